@@ -1,8 +1,9 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
+use std::str::FromStr;
 
 /// Configuration for table formatting.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct FormatConfig {
     /// Maximum number or table columns.
     pub max_columns: usize,
@@ -10,6 +11,10 @@ pub struct FormatConfig {
     pub max_column_width: usize,
     /// Maximum table width, use default if None
     pub max_table_width: Option<usize>,
+    /// Format used to show dataframes.
+    pub output_format: OutputFormat,
+    /// Timezone used to show datetimes, naive datetimes are assumed UTC.
+    pub timezone: Option<String>,
 }
 
 impl Default for FormatConfig {
@@ -18,6 +23,36 @@ impl Default for FormatConfig {
             max_columns: 8,
             max_column_width: 80,
             max_table_width: None,
+            output_format: OutputFormat::Table,
+            timezone: None,
+        }
+    }
+}
+
+/// Format used to show dataframes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable table.
+    #[default]
+    Table,
+    /// Comma separated values with a header.
+    Csv,
+    /// A JSON array of row objects.
+    Json,
+    /// One JSON object per row.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!("Unknown output format '{s}'")),
         }
     }
 }
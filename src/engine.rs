@@ -5,15 +5,24 @@
 use anyhow::{anyhow, bail, Result};
 use polars::prelude::*;
 use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
-use crate::{completions::Completions, config::FormatConfig, parser::Expr};
+use crate::config::{FormatConfig, OutputFormat};
+use crate::error::{self, Error};
+use crate::{completions::Completions, parser::Expr, schemas::SchemaCache, signatures};
 
 mod args;
 mod arrange;
+mod assert;
 mod config;
 mod count;
+mod count_rows;
 mod csv;
 mod distinct;
+mod excel;
+mod expect_schema;
+mod files;
 mod filter;
 mod fmt;
 mod glimpse;
@@ -21,13 +30,20 @@ mod group_by;
 mod head;
 mod joins;
 mod json;
+mod lineage;
 mod mutate;
 mod parquet;
+mod partition;
+mod pivot_count;
 mod relocate;
 mod rename;
+mod sample;
 mod select;
 mod show;
+mod sqlite;
+mod struct_fields;
 mod summarize;
+mod top_n;
 mod unnest;
 
 #[derive(Default)]
@@ -38,6 +54,8 @@ pub struct Context {
     df: Option<LazyFrame>,
     /// Group passed to aggregate functions.
     group: Option<LazyGroupBy>,
+    /// Columns of the active group.
+    group_columns: Vec<String>,
     /// Dataframe columns.
     columns: Vec<String>,
     /// Optional output used for testing.
@@ -46,6 +64,21 @@ pub struct Context {
     format_config: FormatConfig,
     /// Completions lru
     completions: Completions,
+    /// Page long outputs when writing to a terminal.
+    paging: bool,
+    /// Fail conversions that set invalid values to null.
+    strict: bool,
+    /// Maximum number of rows collected by output functions.
+    max_output_rows: Option<usize>,
+    /// Provenance of the active dataframe columns.
+    lineage: lineage::Lineage,
+    /// Provenance of the named data frames columns.
+    vars_lineage: HashMap<String, lineage::Lineage>,
+    /// Schemas used by readers of the given paths when checking a script
+    /// without reading data, `None` when running a script.
+    sample_schemas: Option<HashMap<PathBuf, SchemaRef>>,
+    /// Columns of the files read in the repl input.
+    schemas: SchemaCache,
 }
 
 impl Context {
@@ -54,6 +87,47 @@ impl Context {
         self.completions.iter().map(|s| s.to_string())
     }
 
+    /// Returns the columns of the files read in the input text, the columns are
+    /// empty while the files schemas are loading.
+    pub fn file_completions(&self, text: &str) -> Vec<String> {
+        self.schemas.completions(text)
+    }
+
+    /// Creates a context that checks pipelines against the schemas of sample
+    /// parquet files.
+    ///
+    /// Each `(reader_path, sample_path)` pair sets the schema used by readers
+    /// of `reader_path`, they return an empty dataframe with the schema of the
+    /// sample. Readers of other paths only read their file schema, writers and
+    /// `assert` are skipped, and the output is discarded.
+    pub fn with_sample_schemas(schemas: &[(PathBuf, PathBuf)]) -> Result<Self> {
+        let mut sample_schemas = HashMap::new();
+
+        for (reader_path, sample_path) in schemas {
+            let schema = LazyFrame::scan_parquet(sample_path, ScanArgsParquet::default())
+                .and_then(|mut df| df.schema())
+                .map_err(|e| {
+                    Error::io(format!(
+                        "check error: cannot read schema from '{}' {e}",
+                        sample_path.display()
+                    ))
+                })?;
+
+            sample_schemas.insert(reader_path.clone(), schema);
+        }
+
+        Ok(Self {
+            output: Some(Default::default()),
+            sample_schemas: Some(sample_schemas),
+            ..Default::default()
+        })
+    }
+
+    /// Enables paging of long outputs, used by the interactive repl.
+    pub fn set_paging(&mut self, paging: bool) {
+        self.paging = paging;
+    }
+
     /// Returns the active dataframe variables.
     pub fn vars(&self) -> Vec<String> {
         self.vars.keys().cloned().collect()
@@ -68,6 +142,27 @@ impl Context {
     fn clear(&mut self) {
         self.df = None;
         self.group = None;
+        self.lineage.clear();
+    }
+
+    /// Collects a dataframe, fails if it has more than `max_output_rows` rows.
+    ///
+    /// Only up to `max_output_rows + 1` rows are computed so that a runaway
+    /// join doesn't exhaust memory.
+    fn collect(&self, df: LazyFrame, fname: &str) -> Result<DataFrame> {
+        let Some(max_rows) = self.max_output_rows else {
+            return Ok(df.collect()?);
+        };
+
+        let limit = IdxSize::try_from(max_rows).unwrap_or(IdxSize::MAX);
+        let df = df.limit(limit.saturating_add(1)).collect()?;
+        if df.height() > max_rows {
+            bail!(
+                "{fname} error: output has more than {max_rows} rows, see config max_output_rows"
+            );
+        }
+
+        Ok(df)
     }
 
     /// Returns and consume the input dataframe.
@@ -81,7 +176,7 @@ impl Context {
 
         self.columns = df
             .schema()
-            .map_err(|e| anyhow!("Schema error: {e}"))?
+            .map_err(|e| error::prefix(e, "Schema"))?
             .iter_names()
             .map(|s| s.to_string())
             .collect::<Vec<_>>();
@@ -107,14 +202,19 @@ impl Context {
         self.group.is_some()
     }
 
-    /// Sets the active group.
-    fn set_group(&mut self, group: LazyGroupBy) -> Result<()> {
+    /// Returns the columns of the active group.
+    fn group_columns(&self) -> &[String] {
+        &self.group_columns
+    }
+
+    /// Sets the active group and its columns.
+    fn set_group(&mut self, group: LazyGroupBy, columns: Vec<String>) -> Result<()> {
         assert!(self.df.is_none());
 
         self.columns = group
             .logical_plan
             .compute_schema()
-            .map_err(|e| anyhow!("Schema error: {e}"))?
+            .map_err(|e| error::prefix(e, "Schema"))?
             .iter_names()
             .map(|s| s.to_string())
             .collect::<Vec<_>>();
@@ -122,6 +222,7 @@ impl Context {
         self.update_completions();
 
         self.group = Some(group);
+        self.group_columns = columns;
         Ok(())
     }
 
@@ -129,23 +230,55 @@ impl Context {
         self.completions.add(&self.columns);
     }
 
+    /// Sets the format used to show dataframes.
+    pub fn set_output_format(&mut self, format: OutputFormat) {
+        self.format_config.output_format = format;
+    }
+
     /// Print results to the context output.
-    fn print(&mut self, df: DataFrame) -> Result<()> {
+    fn print(&mut self, mut df: DataFrame) -> Result<()> {
         self.set_fmt();
 
-        if let Some(write) = self.output.as_mut() {
+        if self.format_config.timezone.is_some() {
+            df = self.to_display_timezone(df.lazy())?.collect()?;
+        }
+
+        let format = self.format_config.output_format;
+        if format != OutputFormat::Table {
+            if let Some(write) = self.output.as_mut() {
+                fmt::write_df(write, &mut df, format)?;
+            } else {
+                fmt::write_df(&mut std::io::stdout().lock(), &mut df, format)?;
+            }
+        } else if let Some(write) = self.output.as_mut() {
             fmt::df_test(write, df)?;
         } else {
-            println!("{df}");
+            let table = fmt::table(&df, self.format_config.max_columns);
+            if self.paging && std::io::stdout().is_terminal() {
+                fmt::page(&table, &mut fmt::TerminalPager)?;
+            } else {
+                println!("{table}");
+            }
         }
         Ok(())
     }
 
     /// Show a glimpse view of the datafrmae.
-    fn glimpse(&mut self, df: LazyFrame) -> Result<()> {
+    ///
+    /// The `format` parameter overrides the configured output format.
+    fn glimpse(&mut self, df: LazyFrame, format: Option<OutputFormat>) -> Result<()> {
         self.set_fmt();
 
-        if let Some(write) = self.output.as_mut() {
+        let df = self.to_display_timezone(df)?;
+
+        let format = format.unwrap_or(self.format_config.output_format);
+        if format != OutputFormat::Table {
+            if let Some(write) = self.output.as_mut() {
+                fmt::glimpse_json(write, df)?;
+            } else {
+                fmt::glimpse_json(&mut std::io::stdout(), df)?;
+            }
+        } else if let Some(write) = self.output.as_mut() {
             fmt::glimpse(write, df)?;
         } else {
             fmt::glimpse(&mut std::io::stdout(), df)?;
@@ -154,6 +287,33 @@ impl Context {
         Ok(())
     }
 
+    /// Converts datetime columns to the display timezone if one is configured.
+    fn to_display_timezone(&self, mut df: LazyFrame) -> Result<LazyFrame> {
+        let Some(timezone) = self.format_config.timezone.as_ref() else {
+            return Ok(df);
+        };
+
+        let exprs = df
+            .schema()?
+            .iter()
+            .filter_map(|(name, dtype)| match dtype {
+                DataType::Datetime(_, None) => Some(
+                    col(name)
+                        .dt()
+                        .replace_time_zone(Some("UTC".into()), lit("raise"), NonExistent::Raise)
+                        .dt()
+                        .convert_time_zone(timezone.clone()),
+                ),
+                DataType::Datetime(_, Some(_)) => {
+                    Some(col(name).dt().convert_time_zone(timezone.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        Ok(df.with_columns(exprs))
+    }
+
     fn set_fmt(&self) {
         if let Some(w) = self.format_config.max_table_width {
             std::env::set_var("POLARS_TABLE_WIDTH", w.to_string());
@@ -178,11 +338,12 @@ pub fn eval(ctx: &mut Context, exprs: &[Expr]) -> Result<()> {
 }
 
 /// Evaluate pipelines expressions to a string output, used for testing.
-pub fn eval_to_string(exprs: &[Expr]) -> Result<String> {
+pub fn eval_to_string(exprs: &[Expr], output_format: OutputFormat) -> Result<String> {
     let mut ctx = Context {
         output: Some(Default::default()),
         format_config: FormatConfig {
             max_column_width: 82,
+            output_format,
             ..Default::default()
         },
         ..Default::default()
@@ -195,11 +356,22 @@ pub fn eval_to_string(exprs: &[Expr]) -> Result<String> {
 
 fn eval_pipelines(exprs: &[Expr], ctx: &mut Context) -> Result<()> {
     for expr in exprs {
-        if let Expr::Pipeline(exprs) = expr {
+        if let Expr::Pipeline(exprs, positions) = expr {
             ctx.clear();
 
-            for expr in exprs {
-                eval_pipeline_step(expr, ctx)?;
+            for (idx, (expr, position)) in exprs.iter().zip(positions).enumerate() {
+                // Variables assigned by previous pipelines are kept, the active
+                // dataframe or group of the failed pipeline is dropped.
+                let prev_columns = ctx.columns().clone();
+                let prev_lineage = ctx.lineage.clone();
+
+                if let Err(e) = eval_pipeline_step(expr, ctx) {
+                    ctx.clear();
+                    let err = Error::from(e).in_step(idx + 1, step_name(expr), *position);
+                    return Err(err.into());
+                }
+
+                lineage::update(expr, &prev_columns, prev_lineage, ctx);
             }
         }
     }
@@ -207,40 +379,90 @@ fn eval_pipelines(exprs: &[Expr], ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// Returns the name of a pipeline step used in error messages.
+fn step_name(expr: &Expr) -> &str {
+    match expr {
+        Expr::Function(name, _) | Expr::Identifier(name) => name,
+        _ => "?",
+    }
+}
+
 fn eval_pipeline_step(expr: &Expr, ctx: &mut Context) -> Result<()> {
+    let mut schema_only = false;
+    if let (Some(schemas), Expr::Function(name, args)) = (&ctx.sample_schemas, expr) {
+        match name.as_str() {
+            "csv" | "excel" | "json" | "parquet" | "sqlite" => {
+                // Skip writers.
+                if ctx.df.is_some() || ctx.group.is_some() {
+                    return Ok(());
+                }
+
+                let paths = args::paths(&args[0]);
+                if let Some(schema) = paths.first().and_then(|path| schemas.get(path)) {
+                    let df = DataFrame::from(schema.as_ref()).lazy();
+                    ctx.set_df(df)?;
+                    return Ok(());
+                }
+
+                schema_only = true;
+            }
+            "assert" => return Ok(()),
+            _ => {}
+        }
+    }
+
     match expr {
         Expr::Function(name, args) => match name.as_str() {
+            "anti_filter" => joins::eval_anti_filter(args, ctx)?,
             "anti_join" => joins::eval(args, ctx, JoinType::Anti)?,
             "arrange" => arrange::eval(args, ctx)?,
+            "arrange_within_group" => arrange::eval_within_group(args, ctx)?,
+            "assert" => assert::eval(args, ctx)?,
             "config" => config::eval(args, ctx)?,
             "count" => count::eval(args, ctx)?,
+            "count_rows" => count_rows::eval(args, ctx)?,
             "cross_join" => joins::eval(args, ctx, JoinType::Cross)?,
             "csv" => csv::eval(args, ctx)?,
             "distinct" => distinct::eval(args, ctx)?,
+            "excel" => excel::eval(args, ctx)?,
+            "expect_schema" => expect_schema::eval(args, ctx)?,
             "filter" => filter::eval(args, ctx)?,
             "glimpse" => glimpse::eval(args, ctx)?,
             "group_by" => group_by::eval(args, ctx)?,
             "head" => head::eval(args, ctx)?,
             "inner_join" => joins::eval(args, ctx, JoinType::Inner)?,
             "json" => json::eval(args, ctx)?,
+            "lineage" => lineage::eval(args, ctx)?,
             "left_join" => joins::eval(args, ctx, JoinType::Left)?,
             "mutate" => mutate::eval(args, ctx)?,
             "outer_join" => joins::eval(args, ctx, JoinType::Full)?,
             "parquet" => parquet::eval(args, ctx)?,
+            "pivot_count" => pivot_count::eval(args, ctx)?,
             "relocate" => relocate::eval(args, ctx)?,
             "rename" => rename::eval(args, ctx)?,
+            "reorder_like" => select::eval_like(args, ctx)?,
+            "sample_frac" => sample::eval_frac(args, ctx)?,
+            "sample_n" => sample::eval_n(args, ctx)?,
             "select" => select::eval(args, ctx)?,
             "show" => show::eval(args, ctx)?,
+            "sqlite" => sqlite::eval(args, ctx)?,
+            "struct_fields" => struct_fields::eval(args, ctx)?,
             "summarize" => summarize::eval(args, ctx)?,
-            "unnest" => unnest::eval(args, ctx)?,
-            _ => panic!("Unknown function {name}"),
+            "top_n" => top_n::eval(args, ctx)?,
+            "unnest" => unnest::eval(args, ctx, unnest::UnnestType::Any)?,
+            "unnest_list" => unnest::eval(args, ctx, unnest::UnnestType::List)?,
+            "unnest_struct" => unnest::eval(args, ctx, unnest::UnnestType::Struct)?,
+            _ => return Err(signatures::unknown_function(name)),
         },
         Expr::Identifier(name) => {
             // If there is an input assign it to the variable.
             if let Some(plan) = ctx.take_df() {
                 ctx.vars.insert(name.to_owned(), plan.clone());
+                ctx.vars_lineage
+                    .insert(name.to_owned(), ctx.lineage.clone());
                 ctx.set_df(plan)?;
             } else if let Some(plan) = ctx.vars.get(name) {
+                ctx.lineage = ctx.vars_lineage.get(name).cloned().unwrap_or_default();
                 ctx.set_df(plan.clone())?;
             } else if ctx.is_grouping() {
                 bail!("Cannot assign a group to variable '{name}'");
@@ -251,5 +473,14 @@ fn eval_pipeline_step(expr: &Expr, ctx: &mut Context) -> Result<()> {
         _ => panic!("Unexpected expression {expr}"),
     }
 
+    // Readers without a sample schema only read the schema of their file so
+    // that the following steps never collect its data.
+    if schema_only {
+        if let Some(mut df) = ctx.take_df() {
+            let schema = df.schema().map_err(|e| error::prefix(e, "check"))?;
+            ctx.set_df(DataFrame::from(schema.as_ref()).lazy())?;
+        }
+    }
+
     Ok(())
 }
@@ -4,8 +4,10 @@ use anyhow::{anyhow, bail, Result};
 use polars::export::chrono::prelude::*;
 use polars::lazy::dsl::Expr as PolarsExpr;
 use polars::prelude::*;
+use std::path::PathBuf;
 use std::str::FromStr;
 
+use crate::error::Error;
 use crate::parser::{Expr, Operator};
 
 /// Returns the string from a string expression.
@@ -18,6 +20,26 @@ pub fn string(expr: &Expr) -> String {
     }
 }
 
+/// Returns the paths from a string or a list of strings expression.
+///
+/// Panics if the expression is not a string or a list of strings.
+pub fn paths(expr: &Expr) -> Vec<PathBuf> {
+    match expr {
+        Expr::List(exprs) => exprs.iter().map(|e| PathBuf::from(string(e))).collect(),
+        _ => vec![PathBuf::from(string(expr))],
+    }
+}
+
+/// Returns the numbers from a list of numbers expression.
+///
+/// Panics if the expression is not a list of numbers.
+pub fn numbers(expr: &Expr) -> Vec<f64> {
+    match expr {
+        Expr::List(exprs) => exprs.iter().map(number).collect(),
+        _ => panic!("{expr} is not a list expression"),
+    }
+}
+
 /// Returns the string from an identifier expression.
 ///
 /// Panics if the expression is not an identifier.
@@ -34,7 +56,79 @@ pub fn column(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
     schema
         .get(&column)
         .map(|_| col(&column))
-        .ok_or_else(|| anyhow!("Unknown column '{expr}'"))
+        .ok_or_else(|| unknown_column(&column, schema.iter_names()))
+}
+
+/// Returns a column expression or a literal for `true` and `false` identifiers
+/// that are not columns.
+pub fn column_or_bool(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+    match expr {
+        Expr::Identifier(s) if s == "true" && !schema.contains(s) => Ok(lit(true)),
+        Expr::Identifier(s) if s == "false" && !schema.contains(s) => Ok(lit(false)),
+        _ => column(expr, schema),
+    }
+}
+
+/// Returns an unknown column error with suggestions from the given columns.
+pub fn unknown_column<I, S>(column: &str, columns: I) -> anyhow::Error
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let message = format!("Unknown column '{column}'");
+    did_you_mean(Error::unknown_column(column, columns, message)).into()
+}
+
+/// Adds the closest suggested column to an unknown column error message.
+fn did_you_mean(err: Error) -> Error {
+    if let Error::UnknownColumn {
+        suggestions,
+        message,
+        ..
+    } = &err
+    {
+        if let Some(column) = suggestions.first() {
+            return err.with_message(format!("{message}; did you mean '{column}'?"));
+        }
+    }
+
+    err
+}
+
+/// Checks that all the given columns are in the schema columns.
+///
+/// Returns an error that lists all the unknown columns and the available ones.
+pub fn check_columns(columns: &[String], schema_cols: &[String]) -> Result<()> {
+    let mut unknown = Vec::new();
+    for column in columns {
+        if !schema_cols.contains(column) && !unknown.contains(&column.as_str()) {
+            unknown.push(column.as_str());
+        }
+    }
+
+    if !unknown.is_empty() {
+        let label = if unknown.len() == 1 {
+            "Unknown column"
+        } else {
+            "Unknown columns"
+        };
+
+        let message = format!(
+            "{label}: {}; available: {}",
+            unknown.join(", "),
+            schema_cols.join(", ")
+        );
+        let err = Error::unknown_column(unknown[0], schema_cols, message);
+
+        // Suggest a column only when there is a single unknown one.
+        if unknown.len() == 1 {
+            bail!(did_you_mean(err));
+        }
+
+        bail!(err);
+    }
+
+    Ok(())
 }
 
 /// Returns the value from a number expression.
@@ -66,18 +160,77 @@ pub fn timestamp(expr: &Expr) -> Result<NaiveDateTime> {
 }
 
 pub fn named_bool(args: &[Expr], name: &str) -> Result<bool> {
+    named_bool_opt(args, name).map(|value| value.unwrap_or(false))
+}
+
+/// Returns the value of a named bool variable or `None` if it is not set.
+pub fn named_bool_opt(args: &[Expr], name: &str) -> Result<Option<bool>> {
     for arg in args {
         if let Expr::BinaryOp(lhs, Operator::Assign, rhs) = arg {
             match (lhs.as_ref(), rhs.as_ref()) {
                 (Expr::Identifier(lhs), Expr::Identifier(rhs)) if lhs == name => {
-                    return Ok(bool::from_str(rhs)?);
+                    return Ok(Some(bool::from_str(rhs)?));
                 }
                 _ => {}
             }
         }
     }
 
-    Ok(false)
+    Ok(None)
+}
+
+/// Returns the columns of a named list like `partition_by = year, month`.
+///
+/// The list starts with the named assignment and continues with the following
+/// identifiers, returns an error if an identifier is not part of the list.
+pub fn named_columns(args: &[Expr], name: &str) -> Result<Vec<String>> {
+    let mut columns = Vec::new();
+    let mut in_list = false;
+
+    for arg in args {
+        match arg {
+            Expr::BinaryOp(lhs, Operator::Assign, rhs) => {
+                in_list = false;
+
+                if let (Expr::Identifier(lhs), Expr::Identifier(rhs)) = (lhs.as_ref(), rhs.as_ref())
+                {
+                    if lhs == name {
+                        columns.push(rhs.to_owned());
+                        in_list = true;
+                    }
+                }
+            }
+            Expr::Identifier(column) if in_list => columns.push(column.to_owned()),
+            Expr::Identifier(column) => bail!("unexpected column '{column}'"),
+            _ => {}
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Returns the value of a named string variable like `algorithm = "fnv"`.
+pub fn named_string(args: &[Expr], name: &str) -> Option<String> {
+    args.iter().find_map(|arg| match arg {
+        Expr::BinaryOp(lhs, Operator::Assign, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (Expr::Identifier(lhs), Expr::String(value)) if lhs == name => Some(value.clone()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns the values of a named list of strings like `labels = ["a", "b"]`.
+pub fn named_strings(args: &[Expr], name: &str) -> Option<Vec<String>> {
+    args.iter().find_map(|arg| match arg {
+        Expr::BinaryOp(lhs, Operator::Assign, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+            (Expr::Identifier(lhs), Expr::List(values)) if lhs == name => {
+                Some(values.iter().map(string).collect())
+            }
+            _ => None,
+        },
+        _ => None,
+    })
 }
 
 /// Returns the value of a named integer variable like `schema_rows = 2000`.
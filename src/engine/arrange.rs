@@ -1,6 +1,7 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
 use anyhow::{bail, Result};
+use polars::lazy::dsl::Expr as PolarsExpr;
 use polars::prelude::*;
 
 use crate::parser::Expr;
@@ -11,35 +12,9 @@ use super::*;
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
-    if let Some(df) = ctx.take_df() {
-        let schema_cols = ctx.columns();
-        let mut columns = Vec::with_capacity(args.len());
-        let mut descending = Vec::with_capacity(args.len());
-
-        for arg in args {
-            match arg {
-                Expr::Function(name, args) if name == "desc" => {
-                    // arrange(desc(column))
-                    let column = args::identifier(&args[0]);
-                    if !schema_cols.contains(&column) {
-                        bail!("arrange error: Unknown column {column}");
-                    }
-
-                    columns.push(col(&column));
-                    descending.push(true);
-                }
-                Expr::Identifier(column) => {
-                    // arrange(column)
-                    if !schema_cols.contains(column) {
-                        bail!("arrange error: Unknown column {column}");
-                    }
-
-                    columns.push(col(column));
-                    descending.push(false);
-                }
-                _ => {}
-            }
-        }
+    if let Some(mut df) = ctx.take_df() {
+        let schema = df.schema().map_err(|e| error::prefix(e, "arrange"))?;
+        let (columns, descending) = sort_keys(args, &schema, "arrange")?;
 
         let sort_opts = SortMultipleOptions {
             descending,
@@ -56,3 +31,100 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
 
     Ok(())
 }
+
+/// Evaluates an arrange_within_group call.
+///
+/// Rows are sorted within their groups and the groups keep the order in which
+/// they first appear.
+pub fn eval_within_group(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(group) = ctx.take_group() {
+        let mut df = LazyFrame::from(group.logical_plan);
+        let schema = df
+            .schema()
+            .map_err(|e| error::prefix(e, "arrange_within_group"))?;
+        let (mut columns, mut descending) = sort_keys(args, &schema, "arrange_within_group")?;
+
+        // Sort first by the position of the first row of each group using a
+        // row index column that is not in the schema.
+        let mut row_col = "row".to_string();
+        while schema.contains(&row_col) {
+            row_col.insert(0, '_');
+        }
+
+        let group_columns = ctx
+            .group_columns()
+            .iter()
+            .map(|c| col(c))
+            .collect::<Vec<_>>();
+        columns.insert(0, col(&row_col).min().over(group_columns));
+        descending.insert(0, false);
+
+        let sort_opts = SortMultipleOptions {
+            descending,
+            nulls_last: vec![true],
+            maintain_order: true,
+            ..Default::default()
+        };
+
+        let df = df
+            .with_row_index(&row_col, None)
+            .sort_by_exprs(columns, sort_opts)
+            .drop([&row_col]);
+        ctx.set_df(df)?;
+    } else if ctx.take_df().is_some() {
+        bail!("arrange_within_group error: must be called after a group_by, use arrange");
+    } else {
+        bail!("arrange_within_group error: missing input group");
+    }
+
+    Ok(())
+}
+
+/// Returns the sort expressions and their descending flags.
+fn sort_keys(args: &[Expr], schema: &Schema, fname: &str) -> Result<(Vec<PolarsExpr>, Vec<bool>)> {
+    let mut columns = Vec::with_capacity(args.len());
+    let mut descending = Vec::with_capacity(args.len());
+
+    for arg in args {
+        match arg {
+            Expr::Function(name, args) if name == "desc" => {
+                // arrange(desc(column)) or arrange(desc(ci(column)))
+                columns.push(sort_key(&args[0], schema, fname)?);
+                descending.push(true);
+            }
+            arg => {
+                // arrange(column) or arrange(ci(column))
+                columns.push(sort_key(arg, schema, fname)?);
+                descending.push(false);
+            }
+        }
+    }
+
+    Ok((columns, descending))
+}
+
+/// Returns the expression used to sort a column.
+///
+/// Columns wrapped in `ci` are sorted by their lowercase values so that the
+/// ordering ignores case, the output keeps the original values.
+fn sort_key(arg: &Expr, schema: &Schema, fname: &str) -> Result<PolarsExpr> {
+    let (column, ignore_case) = match arg {
+        Expr::Function(name, args) if name == "ci" => (args::identifier(&args[0]), true),
+        arg => (args::identifier(arg), false),
+    };
+
+    let Some(dtype) = schema.get(&column) else {
+        return Err(error::prefix(
+            args::unknown_column(&column, schema.iter_names()),
+            fname,
+        ));
+    };
+
+    if !ignore_case {
+        Ok(col(&column))
+    } else if dtype == &DataType::String {
+        Ok(col(&column).str().to_lowercase())
+    } else {
+        bail!("{fname} error: `ci` column '{column}' must be a string, found {dtype}");
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::{Expr, Operator};
+
+use super::*;
+
+/// Maximum number of failing rows shown in an assert error.
+const SAMPLE_ROWS: u32 = 5;
+
+/// Evaluates an assert call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(mut df) = ctx.take_df() {
+        let schema = df.schema().map_err(|e| error::prefix(e, "assert"))?;
+
+        for arg in args {
+            match arg {
+                // assert(max(total_amount) < 10000)
+                Expr::BinaryOp(lhs, _, _) if is_aggregate(lhs) => {
+                    check_aggregate(&df, arg, &schema)?
+                }
+                // assert(!is_null(passenger_count))
+                _ => check_rows(&df, arg, &schema)?,
+            }
+        }
+
+        ctx.set_df(df)?;
+    } else if ctx.is_grouping() {
+        bail!("assert error: must call summarize after a group_by");
+    } else {
+        bail!("assert error: missing input dataframe");
+    }
+
+    Ok(())
+}
+
+fn is_aggregate(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Function(name, _)
+            if ["max", "mean", "median", "min", "n", "sd", "sum", "var"].contains(&name.as_str())
+    )
+}
+
+/// Checks a comparison between an aggregate of all the rows and a number.
+fn check_aggregate(df: &LazyFrame, expr: &Expr, schema: &Schema) -> Result<()> {
+    let Expr::BinaryOp(lhs, op, rhs) = expr else {
+        panic!("Unexpected assert expression {expr}");
+    };
+
+    let value =
+        summarize::eval_aggregate(lhs, schema, false).map_err(|e| error::prefix(e, "assert"))?;
+    let bound = lit(args::number(rhs));
+
+    let check = match op {
+        Operator::Eq => value.clone().eq(bound),
+        Operator::NotEq => value.clone().neq(bound),
+        Operator::Lt => value.clone().lt(bound),
+        Operator::LtEq => value.clone().lt_eq(bound),
+        Operator::Gt => value.clone().gt(bound),
+        Operator::GtEq => value.clone().gt_eq(bound),
+        _ => panic!("Unexpected assert operator {op}"),
+    };
+
+    let result = df
+        .clone()
+        .select([value.alias("value"), check.alias("check")])
+        .collect()
+        .map_err(|e| error::prefix(e, "assert"))?;
+
+    if !result.column("check")?.bool()?.get(0).unwrap_or(false) {
+        let value = result.column("value")?.get(0)?;
+        bail!("assert error: `{expr}` failed, {lhs} is {value}");
+    }
+
+    Ok(())
+}
+
+/// Checks that all the rows satisfy a predicate, rows where the predicate is
+/// null are failures.
+fn check_rows(df: &LazyFrame, expr: &Expr, schema: &Schema) -> Result<()> {
+    let predicate = filter::eval_condition(expr, schema).map_err(|e| error::prefix(e, "assert"))?;
+
+    // 1-based row numbers using a name that is not in the schema.
+    let mut row_col = "row".to_string();
+    while schema.contains(&row_col) {
+        row_col.insert(0, '_');
+    }
+
+    let failed = df
+        .clone()
+        .with_row_index(&row_col, Some(1))
+        .filter(predicate.fill_null(lit(false)).not());
+
+    let count = failed
+        .clone()
+        .select([len()])
+        .collect()
+        .map_err(|e| error::prefix(e, "assert"))?;
+    let count = count[0].u32()?.get(0).unwrap_or_default();
+    if count == 0 {
+        return Ok(());
+    }
+
+    let mut columns = Vec::new();
+    predicate_columns(expr, schema, &mut columns);
+
+    let mut select = vec![col(&row_col)];
+    select.extend(columns.iter().map(|c| col(c)));
+
+    let sample = failed
+        .select(select)
+        .limit(SAMPLE_ROWS)
+        .collect()
+        .map_err(|e| error::prefix(e, "assert"))?;
+
+    let mut rows = Vec::with_capacity(sample.height());
+    for idx in 0..sample.height() {
+        let row = sample[0].get(idx)?;
+        let values = columns
+            .iter()
+            .map(|c| Ok(format!("{c} = {}", sample.column(c)?.get(idx)?)))
+            .collect::<Result<Vec<_>>>()?;
+        rows.push(format!("row {row}: {}", values.join(", ")));
+    }
+
+    let label = if count == 1 { "row" } else { "rows" };
+    bail!(
+        "assert error: `{expr}` failed for {count} {label}, {}",
+        rows.join("; ")
+    );
+}
+
+/// Collects the schema columns used by a predicate.
+fn predicate_columns(expr: &Expr, schema: &Schema, columns: &mut Vec<String>) {
+    match expr {
+        Expr::Identifier(name) if schema.contains(name) && !columns.contains(name) => {
+            columns.push(name.clone());
+        }
+        Expr::Function(_, args) => args
+            .iter()
+            .for_each(|arg| predicate_columns(arg, schema, columns)),
+        Expr::BinaryOp(lhs, _, rhs) => {
+            predicate_columns(lhs, schema, columns);
+            predicate_columns(rhs, schema, columns);
+        }
+        Expr::UnaryOp(_, expr) => predicate_columns(expr, schema, columns),
+        _ => {}
+    }
+}
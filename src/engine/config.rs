@@ -1,5 +1,7 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
+use polars::chunked_array::temporal::validate_time_zone;
+
 use crate::parser::Expr;
 
 use super::*;
@@ -20,5 +22,23 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
         ctx.format_config.max_table_width = if value > 0 { Some(value) } else { None };
     }
 
+    if let Ok(Some(value)) = args::named_usize(args, "max_output_rows") {
+        ctx.max_output_rows = if value > 0 { Some(value) } else { None };
+    }
+
+    if let Some(timezone) = args::named_string(args, "timezone") {
+        if timezone.is_empty() {
+            ctx.format_config.timezone = None;
+        } else {
+            validate_time_zone(&timezone)
+                .map_err(|_| anyhow!("config error: unknown timezone '{timezone}'"))?;
+            ctx.format_config.timezone = Some(timezone);
+        }
+    }
+
+    if let Some(strict) = args::named_bool_opt(args, "strict")? {
+        ctx.strict = strict;
+    }
+
     Ok(())
 }
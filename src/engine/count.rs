@@ -18,7 +18,8 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
         for arg in args {
             if let Expr::Identifier(column) = arg {
                 if !schema_cols.contains(column) {
-                    bail!("count error: Unknown column {column}");
+                    let err = args::unknown_column(column, schema_cols);
+                    return Err(error::prefix(err, "count"));
                 }
 
                 let expr = col(column);
@@ -31,13 +32,11 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
         let agg_col = find_agg_column(schema_cols.as_slice());
 
         let df = if !columns.is_empty() {
-            let ncol = columns.last().unwrap().clone();
-            let df = df
-                .group_by(&columns)
-                .agg([ncol.is_not_null().count().alias(&agg_col)]);
+            let df = df.group_by(&columns).agg([len().alias(&agg_col)]);
 
             let mut descending = vec![false; columns.len()];
 
+            // When sorting by count the group columns break ties.
             if args::named_bool(args, "sort")? {
                 columns.insert(0, col(&agg_col));
                 descending.insert(0, true);
@@ -50,7 +49,26 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
 
             df.sort_by_exprs(columns, sort_opts)
         } else {
-            df.select(&[col(&schema_cols[0]).count().alias(&agg_col)])
+            df.select(&[col(&schema_cols[0]).len().alias(&agg_col)])
+        };
+
+        // Proportions of the total count, the cumulative one follows the rows order.
+        let total = col(&agg_col).sum().cast(DataType::Float64);
+        let mut props = Vec::new();
+        if args::named_bool(args, "prop")? {
+            let prop = col(&agg_col).cast(DataType::Float64) / total.clone();
+            props.push(prop.alias("prop"));
+        }
+
+        if args::named_bool(args, "cum_prop")? {
+            let cum_prop = col(&agg_col).cum_sum(false).cast(DataType::Float64) / total;
+            props.push(cum_prop.alias("cum_prop"));
+        }
+
+        let df = if props.is_empty() {
+            df
+        } else {
+            df.with_columns(props)
         };
 
         ctx.set_df(df)?;
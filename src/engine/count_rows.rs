@@ -0,0 +1,29 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates a count_rows call.
+///
+/// Only the number of rows is computed, the columns values are not collected.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(_args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(df) = ctx.take_df() {
+        // The count of a file scan can ignore the alias so the column is renamed
+        // after the collect.
+        let mut df = df.select([len()]).collect()?;
+        df.set_column_names(&["n"])?;
+        ctx.print(df)?;
+    } else if ctx.is_grouping() {
+        bail!("count_rows error: must call summarize after a group_by");
+    } else {
+        bail!("count_rows error: missing input dataframe");
+    }
+
+    Ok(())
+}
@@ -1,8 +1,7 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use polars::prelude::*;
-use std::path::PathBuf;
 
 use crate::parser::Expr;
 
@@ -12,29 +11,85 @@ use super::*;
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
-    // csv("nyctaxi.csv")
-    let path = PathBuf::from(args::string(&args[0]));
+    // csv("nyctaxi.csv") or csv(["jan.csv", "feb.csv"])
+    let paths = args::paths(&args[0]);
     // csv("nyctaxi.csv", overwrite = true)
     let overwrite = args::named_bool(args, "overwrite")?;
+    // csv("out", partition_by = year, month)
+    let partition_cols =
+        args::named_columns(args, "partition_by").map_err(|e| error::prefix(e, "csv"))?;
+    // csv("nyctaxi.csv", null_values("", "NA"))
+    let null_values = args.iter().find_map(|arg| match arg {
+        Expr::Function(name, args) if name == "null_values" => {
+            Some(args.iter().map(args::string).collect::<Vec<_>>())
+        }
+        _ => None,
+    });
 
     // If there is an input dataframe save it to disk.
     if let Some(df) = ctx.take_df() {
+        if null_values.is_some() {
+            bail!("csv error: null_values can only be used when reading a file");
+        }
+
+        let path = match paths.as_slice() {
+            [path] => path,
+            _ => bail!(Error::io(
+                "csv error: cannot write to a list of files".to_string()
+            )),
+        };
+
+        if !partition_cols.is_empty() {
+            args::check_columns(&partition_cols, ctx.columns())
+                .map_err(|e| error::prefix(e, "csv"))?;
+
+            partition::write(
+                path,
+                df.clone(),
+                &partition_cols,
+                overwrite,
+                "csv",
+                |df| ctx.collect(df, "csv"),
+                |file, df| CsvWriter::new(file).finish(df),
+            )
+            .map_err(|e| error::prefix(e, "csv"))?;
+
+            ctx.set_df(df)?;
+
+            return Ok(());
+        }
+
         if !overwrite && path.exists() {
-            bail!("csv error: file '{}' already exists", path.display());
+            bail!(Error::io(format!(
+                "csv error: file '{}' already exists",
+                path.display()
+            )));
         }
 
-        let file = std::fs::File::create(&path)
-            .map_err(|e| anyhow!("csv error: cannot create file '{}' {e}", path.display()))?;
+        let file = std::fs::File::create(path).map_err(|e| {
+            Error::io(format!(
+                "csv error: cannot create file '{}' {e}",
+                path.display()
+            ))
+        })?;
 
-        let mut out_df = df.clone().collect()?;
+        let mut out_df = ctx.collect(df.clone(), "csv")?;
         ctx.set_df(df)?;
 
         CsvWriter::new(file).finish(&mut out_df)?;
     } else {
-        let reader = LazyCsvReader::new(&path).with_infer_schema_length(Some(1000));
-        let df = reader
-            .finish()
-            .map_err(|e| anyhow!("csv error: cannot read file '{}' {e}", path.display()))?;
+        let df = files::read("csv", &paths, |path| {
+            let reader = LazyCsvReader::new(path)
+                .with_infer_schema_length(Some(1000))
+                .with_null_values(null_values.clone().map(NullValues::AllColumns));
+            let df = reader.finish().map_err(|e| {
+                Error::io(format!(
+                    "csv error: cannot read file '{}' {e}",
+                    path.display()
+                ))
+            })?;
+            Ok(df)
+        })?;
         ctx.set_df(df)?;
     }
 
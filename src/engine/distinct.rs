@@ -13,19 +13,22 @@ use super::*;
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(df) = ctx.take_df() {
         let schema_cols = ctx.columns();
+
+        // Report all unknown columns in one go.
+        let names = args.iter().map(args::identifier).collect::<Vec<_>>();
+        args::check_columns(&names, schema_cols).map_err(|e| error::prefix(e, "distinct"))?;
+
         let mut select_columns = Vec::new();
 
         for arg in args {
             let column = args::identifier(arg);
-            if !schema_cols.contains(&column) {
-                bail!("distinct error: Unknown column {column}");
-            }
-
             if !select_columns.contains(&column) {
                 select_columns.push(column);
             }
         }
 
+        // Keep the first row of each group of duplicates in the input order so
+        // that the order of a previous `arrange` is preserved.
         let df = if !select_columns.is_empty() {
             let columns = select_columns.iter().map(|c| col(c)).collect::<Vec<_>>();
             df.select(&columns)
@@ -0,0 +1,165 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+use std::path::Path;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates an excel call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if ctx.take_df().is_some() {
+        bail!("excel error: writing excel files is not supported");
+    }
+
+    // excel("sales.xlsx")
+    let path = args::string(&args[0]);
+    // excel("sales.xlsx", sheet = "regions")
+    let sheet = args::named_string(args, "sheet");
+    // excel("sales.xlsx", header_row = 2)
+    let header_row = args::named_usize(args, "header_row")
+        .map_err(|e| error::prefix(e, "excel"))?
+        .unwrap_or(1);
+    if header_row == 0 {
+        bail!("excel error: header_row must be greater than 0");
+    }
+
+    let df = read(Path::new(&path), sheet.as_deref(), header_row)?;
+    ctx.set_df(df.lazy())?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "excel"))]
+fn read(_path: &Path, _sheet: Option<&str>, _header_row: usize) -> Result<DataFrame> {
+    bail!("excel error: dply was built without the excel feature");
+}
+
+/// Reads a worksheet into a dataframe.
+///
+/// The header is read from the 1-based `header_row` and the rows below it are
+/// the data, columns that only have dates are converted to datetimes, columns
+/// that only have numbers to i64 or f64, and all the other columns to strings.
+#[cfg(feature = "excel")]
+fn read(path: &Path, sheet: Option<&str>, header_row: usize) -> Result<DataFrame> {
+    use anyhow::anyhow;
+    use calamine::{open_workbook_auto, Data, DataType as _, Reader};
+
+    let mut workbook = open_workbook_auto(path).map_err(|e| {
+        Error::io(format!(
+            "excel error: cannot read file '{}' {e}",
+            path.display()
+        ))
+    })?;
+
+    let names = workbook.sheet_names();
+    let sheet = match sheet {
+        Some(sheet) if names.iter().any(|name| name == sheet) => sheet.to_string(),
+        Some(sheet) => bail!(
+            "excel error: sheet '{sheet}' not found, available: {}",
+            names.join(", ")
+        ),
+        None => names
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("excel error: file '{}' has no sheets", path.display()))?,
+    };
+
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| anyhow!("excel error: cannot read sheet '{sheet}' {e}"))?;
+
+    // Range positions are 0-based and the range starts at the first used cell.
+    let (Some((start_row, start_col)), Some((end_row, end_col))) = (range.start(), range.end())
+    else {
+        bail!("excel error: sheet '{sheet}' is empty");
+    };
+
+    let header_idx = header_row - 1;
+    if header_idx < start_row as usize || header_idx > end_row as usize {
+        bail!(
+            "excel error: header row {header_row} is outside the range {}:{} of sheet '{sheet}'",
+            cell_name(start_row, start_col),
+            cell_name(end_row, end_col)
+        );
+    }
+
+    let rows = range.rows().skip(header_idx - start_row as usize);
+    let mut rows = rows.collect::<Vec<_>>();
+    let header = rows.remove(0);
+
+    let mut columns = Vec::with_capacity(header.len());
+    for (idx, name) in header.iter().enumerate() {
+        let name = match name {
+            Data::Empty => format!("column_{}", start_col as usize + idx + 1),
+            name => name.to_string(),
+        };
+
+        let cells = rows.iter().map(|row| &row[idx]).collect::<Vec<_>>();
+        if let Some(pos) = cells.iter().position(|c| matches!(c, Data::Error(_))) {
+            bail!(
+                "excel error: cannot read cell {} of sheet '{sheet}': {}",
+                cell_name((header_idx + pos + 1) as u32, start_col + idx as u32),
+                cells[pos]
+            );
+        }
+
+        let values = || cells.iter().filter(|c| !matches!(c, Data::Empty));
+        let series = if values().count() > 0
+            && values().all(|c| matches!(c, Data::DateTime(dt) if dt.is_datetime()))
+        {
+            let millis = cells
+                .iter()
+                .map(|c| match c {
+                    Data::DateTime(dt) => dt.as_datetime().map(|t| t.and_utc().timestamp_millis()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            Series::new(&name, millis).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?
+        } else if values().count() > 0
+            && values().all(|c| matches!(c, Data::Int(_) | Data::Float(_)))
+        {
+            let floats = cells.iter().map(|c| c.as_f64()).collect::<Vec<_>>();
+            if floats.iter().flatten().all(|v| v.fract() == 0.0) {
+                let ints = floats.iter().map(|v| v.map(|v| v as i64));
+                Series::new(&name, ints.collect::<Vec<_>>())
+            } else {
+                Series::new(&name, floats)
+            }
+        } else {
+            let strings = cells
+                .iter()
+                .map(|c| match c {
+                    Data::Empty => None,
+                    Data::DateTime(dt) if dt.is_datetime() => {
+                        dt.as_datetime().map(|t| t.to_string())
+                    }
+                    c => Some(c.to_string()),
+                })
+                .collect::<Vec<_>>();
+            Series::new(&name, strings)
+        };
+
+        columns.push(series);
+    }
+
+    DataFrame::new(columns).map_err(|e| anyhow!("excel error: {e}"))
+}
+
+/// Returns the A1 name of a 0-based cell position.
+#[cfg(feature = "excel")]
+fn cell_name(row: u32, col: u32) -> String {
+    let mut name = String::new();
+    let mut col = col + 1;
+    while col > 0 {
+        let rem = (col - 1) % 26;
+        name.insert(0, (b'A' + rem as u8) as char);
+        col = (col - 1) / 26;
+    }
+
+    format!("{name}{}", row + 1)
+}
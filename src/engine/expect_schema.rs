@@ -0,0 +1,70 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+
+use crate::parser::{Expr, Operator};
+
+use super::*;
+
+/// Evaluates an expect_schema call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(mut df) = ctx.take_df() {
+        let schema = df.schema().map_err(|e| error::prefix(e, "expect_schema"))?;
+
+        // expect_schema(trip_distance = f64, pickup = "datetime[ns]"), a bool
+        // value is the `exact` option as types are never true or false so
+        // that a column named `exact` can still be checked.
+        let mut exact = false;
+        let mut expected = Vec::with_capacity(args.len());
+        for arg in args {
+            if let Expr::BinaryOp(lhs, Operator::Assign, rhs) = arg {
+                let dtype = match rhs.as_ref() {
+                    Expr::String(dtype) => dtype.clone(),
+                    Expr::Identifier(value) if value == "true" || value == "false" => {
+                        exact = value == "true";
+                        continue;
+                    }
+                    rhs => args::identifier(rhs),
+                };
+                expected.push((args::identifier(lhs), dtype));
+            }
+        }
+
+        // Collect all the violations so that they are reported at once.
+        let mut violations = Vec::new();
+        for (column, dtype) in &expected {
+            match schema.get(column) {
+                Some(found) if &found.to_string() != dtype => {
+                    violations.push(format!("column '{column}' is {found}, expected {dtype}"));
+                }
+                Some(_) => {}
+                None => violations.push(format!("column '{column}' is missing")),
+            }
+        }
+
+        if exact {
+            for column in schema.iter_names() {
+                if !expected.iter().any(|(name, _)| name == column.as_str()) {
+                    violations.push(format!("column '{column}' is not expected"));
+                }
+            }
+        }
+
+        if !violations.is_empty() {
+            bail!(
+                "expect_schema error: schema mismatch: {}",
+                violations.join("; ")
+            );
+        }
+
+        ctx.set_df(df)?;
+    } else if ctx.is_grouping() {
+        bail!("expect_schema error: must call summarize after a group_by");
+    } else {
+        bail!("expect_schema error: missing input dataframe");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+
+/// Reads one or more files and concatenates them in the given order.
+///
+/// All the files must have the same schema as the first one.
+pub fn read<F>(fname: &str, paths: &[PathBuf], read: F) -> Result<LazyFrame>
+where
+    F: Fn(&Path) -> Result<LazyFrame>,
+{
+    let mut frames = Vec::with_capacity(paths.len());
+    let mut first_schema: Option<SchemaRef> = None;
+
+    for path in paths {
+        let mut df = read(path)?;
+        let schema = df.schema().map_err(|e| {
+            Error::io(format!(
+                "{fname} error: cannot read file '{}' {e}",
+                path.display()
+            ))
+        })?;
+
+        match &first_schema {
+            Some(first) if first != &schema => bail!(
+                "{fname} error: file '{}' schema doesn't match file '{}'",
+                path.display(),
+                paths[0].display()
+            ),
+            Some(_) => {}
+            None => first_schema = Some(schema),
+        }
+
+        frames.push(df);
+    }
+
+    if frames.len() == 1 {
+        Ok(frames.remove(0))
+    } else {
+        Ok(concat(frames, UnionArgs::default())?)
+    }
+}
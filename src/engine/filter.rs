@@ -26,12 +26,13 @@ use super::*;
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(mut df) = ctx.take_df() {
+        let schema = df.schema().map_err(|e| error::prefix(e, "filter"))?;
         for arg in args {
-            let expr = df
-                .schema()
-                .map_err(anyhow::Error::from)
-                .and_then(|schema| eval_expr(arg, &schema))
-                .map_err(|e| anyhow!("filter error: {e}"))?;
+            check_types(arg, &schema).map_err(|e| error::prefix(e, "filter"))?;
+        }
+
+        for arg in args {
+            let expr = eval_condition(arg, &schema).map_err(|e| error::prefix(e, "filter"))?;
             df = df.filter(expr);
         }
 
@@ -45,8 +46,18 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
-fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+/// Evaluates a predicate expression to a boolean Polars expression.
+pub fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
     match expr {
+        Expr::BinaryOp(lhs, op @ (Operator::And | Operator::Or), rhs) => {
+            let lhs = eval_condition(lhs, schema)?;
+            let rhs = eval_condition(rhs, schema)?;
+
+            match op {
+                Operator::And => Ok(lhs.and(rhs)),
+                _ => Ok(lhs.or(rhs)),
+            }
+        }
         Expr::BinaryOp(lhs, op, rhs) => {
             let lhs = eval_expr(lhs, schema)?;
             let rhs = eval_expr(rhs, schema)?;
@@ -58,26 +69,105 @@ fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
                 Operator::LtEq => lhs.lt_eq(rhs),
                 Operator::Gt => lhs.gt(rhs),
                 Operator::GtEq => lhs.gt_eq(rhs),
-                Operator::And => lhs.and(rhs),
-                Operator::Or => lhs.or(rhs),
                 _ => panic!("Unexpected filter operator {op}"),
             };
 
             Ok(result)
         }
-        Expr::Identifier(_) => args::column(expr, schema),
+        Expr::Identifier(_) => args::column_or_bool(expr, schema),
         Expr::String(s) => Ok(lit(s.clone())),
         Expr::Number(n) => Ok(lit(*n)),
         Expr::Function(name, args) if name == "dt" => {
             let ts = args::timestamp(&args[0])?;
             Ok(lit(ts))
         }
+        Expr::Function(name, args) if name == "len" => mutate::len(&args[0], schema),
         Expr::UnaryOp(Operator::Not, expr) => eval_predicate(expr, schema).map(|expr| expr.not()),
         Expr::Function(_, _) => eval_predicate(expr, schema),
         _ => panic!("Unexpected filter expression {expr}"),
     }
 }
 
+/// Evaluates a filter condition, a bare identifier must be a boolean column.
+pub fn eval_condition(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+    match expr {
+        Expr::Identifier(column) => match schema.get(column) {
+            Some(DataType::Boolean) => Ok(col(column)),
+            Some(dtype) => Err(anyhow!(
+                "column '{column}' must be a boolean, found {dtype}"
+            )),
+            None => Err(args::unknown_column(column, schema.iter_names())),
+        },
+        _ => eval_expr(expr, schema),
+    }
+}
+
+/// Checks that the predicates arguments types match their columns types.
+///
+/// The check runs on all the predicates before any expression is built so that
+/// a mismatch is reported with the column and argument types.
+fn check_types(expr: &Expr, schema: &Schema) -> Result<()> {
+    use DataType::*;
+
+    let kind = |e: &Expr| match e {
+        Expr::Number(_) => "a number",
+        Expr::String(_) => "a string",
+        _ => "a date",
+    };
+
+    match expr {
+        Expr::BinaryOp(lhs, Operator::And | Operator::Or, rhs) => {
+            check_types(lhs, schema)?;
+            check_types(rhs, schema)?;
+        }
+        Expr::UnaryOp(Operator::Not, expr) => check_types(expr, schema)?,
+        Expr::Function(name, args) if name == "contains" || name == "between" => {
+            // Unknown columns are reported when the predicate is evaluated.
+            let column = args::identifier(&args[0]);
+            let Some(dtype) = schema.get(&column) else {
+                return Ok(());
+            };
+
+            if name == "contains" {
+                let pattern = &args[1];
+                let mismatch = match dtype {
+                    String | Categorical(_, _) | Enum(_, _) => !matches!(pattern, Expr::String(_)),
+                    List(elem_type) if elem_type.is_numeric() => {
+                        !matches!(pattern, Expr::Number(_))
+                    }
+                    List(elem_type) if elem_type.is_string() => !matches!(pattern, Expr::String(_)),
+                    _ => false,
+                };
+
+                if mismatch {
+                    bail!(
+                        "contains: column '{column}' is {dtype} but pattern is {}",
+                        kind(pattern)
+                    );
+                }
+            } else {
+                for (bound, label) in args[1..].iter().zip(["lower", "upper"]) {
+                    let mismatch = match bound {
+                        Expr::String(_) => dtype.is_numeric(),
+                        Expr::Number(_) => dtype.is_string(),
+                        _ => false,
+                    };
+
+                    if mismatch {
+                        bail!(
+                            "between: column '{column}' is {dtype} but {label} bound is {}",
+                            kind(bound)
+                        );
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
 fn eval_predicate(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
     match expr {
         Expr::Function(name, args) if name == "contains" => {
@@ -88,21 +178,90 @@ fn eval_predicate(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
 
             match column_type {
                 DataType::List(elem_type) => list_contains(&column, &args[1], elem_type),
-                DataType::String => string_contains(&column, &args[1]),
+                DataType::String => string_contains(col(&column), &column, &args[1]),
+                DataType::Categorical(_, _) | DataType::Enum(_, _) => {
+                    let expr = col(&column).cast(DataType::String);
+                    string_contains(expr, &column, &args[1])
+                }
                 _ => Err(anyhow!("Column '{column}' must be a str or a list")),
             }
         }
         Expr::Function(name, args) if name == "is_null" => {
-            let r = args::column(&args[0], schema).map(|c| c.is_null());
-            println!("{r:?}");
-            r
+            args::column(&args[0], schema).map(|c| c.is_null())
+        }
+        Expr::Function(name, args) if name == "is_empty" => {
+            let column = args::identifier(&args[0]);
+            match schema.get(&column) {
+                Some(DataType::String) => Ok(col(&column).eq(lit(""))),
+                Some(DataType::List(_)) => Ok(col(&column).list().len().eq(lit(0))),
+                Some(_) => Err(anyhow!("Column '{column}' must be a str or a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "between" => {
+            let column = args::identifier(&args[0]);
+            let Some(dtype) = schema.get(&column) else {
+                return Err(args::unknown_column(&column, schema.iter_names()));
+            };
+
+            // Datetime columns must use dt bounds, between(col, dt("a"), dt("b")).
+            let is_dt = |e: &Expr| matches!(e, Expr::Function(name, _) if name == "dt");
+            let is_datetime = matches!(dtype, DataType::Date | DataType::Datetime(_, _));
+            if is_datetime && !(is_dt(&args[1]) && is_dt(&args[2])) {
+                bail!("between bounds for column '{column}' must be dt values");
+            } else if !is_datetime && (is_dt(&args[1]) || is_dt(&args[2])) {
+                bail!("between column '{column}' must be a date or datetime, found {dtype}");
+            } else if let (Expr::Function(_, lo), Expr::Function(_, hi)) = (&args[1], &args[2]) {
+                let lo = args::timestamp(&lo[0])?;
+                let hi = args::timestamp(&hi[0])?;
+                if lo > hi {
+                    bail!("between lower bound {lo} is after upper bound {hi}");
+                }
+            }
+
+            let lo = eval_expr(&args[1], schema)?;
+            let hi = eval_expr(&args[2], schema)?;
+            Ok(col(&column).gt_eq(lo).and(col(&column).lt_eq(hi)))
+        }
+        Expr::Function(name, args) if name == "is_weekend" => {
+            // ISO weekdays, Monday is 1 and Sunday is 7.
+            let weekend = Series::new("weekend", [6i8, 7]);
+            datetime_column(&args[0], schema).map(|c| c.dt().weekday().is_in(lit(weekend)))
+        }
+        Expr::Function(name, args) if name == "is_business_day" => {
+            let weekdays = Series::new("weekdays", [1i8, 2, 3, 4, 5]);
+            let mut expr = datetime_column(&args[0], schema)?;
+            let is_weekday = expr.clone().dt().weekday().is_in(lit(weekdays));
+
+            // Optional holidays dates, is_business_day(col, "2022-11-24").
+            let holidays = args[1..]
+                .iter()
+                .map(|arg| args::timestamp(arg).map(|ts| ts.date()))
+                .collect::<Result<Vec<_>>>()?;
+
+            if holidays.is_empty() {
+                Ok(is_weekday)
+            } else {
+                let holidays = Series::new("holidays", holidays);
+                expr = expr.dt().date().is_in(lit(holidays)).not();
+                Ok(is_weekday.and(expr))
+            }
         }
         _ => panic!("Unexpected filter expression {expr}"),
     }
 }
 
+/// Returns a column expression for a date or datetime column.
+fn datetime_column(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+    let column = args::identifier(expr);
+    match schema.get(&column) {
+        Some(DataType::Date) | Some(DataType::Datetime(_, _)) => Ok(col(&column)),
+        Some(_) => Err(anyhow!("Column '{column}' must be a date or datetime")),
+        None => Err(args::unknown_column(&column, schema.iter_names())),
+    }
+}
+
 fn list_contains(column: &str, pattern: &Expr, elem_type: &DataType) -> Result<PolarsExpr> {
-    println!("contain {elem_type}");
     use DataType::*;
 
     match (elem_type, pattern) {
@@ -116,7 +275,7 @@ fn list_contains(column: &str, pattern: &Expr, elem_type: &DataType) -> Result<P
         (UInt64, Expr::Number(n)) => Ok(col(column).list().contains(lit(*n as u64))),
         (Float32, Expr::Number(n)) => Ok(col(column).list().contains(lit(*n as f32))),
         (Float64, Expr::Number(n)) => Ok(col(column).list().contains(lit(*n))),
-        (String, Expr::String(s)) => {
+        (String | Categorical(_, _) | Enum(_, _), Expr::String(s)) => {
             let re = regex::Regex::new(s)
                 .map_err(|_| anyhow!("invalid contains regex '{s}' for column '{column}'"))?;
 
@@ -126,7 +285,8 @@ fn list_contains(column: &str, pattern: &Expr, elem_type: &DataType) -> Result<P
 
                 ca.into_iter().for_each(|arr| {
                     let found = if let Some(s) = arr {
-                        s.str()
+                        s.cast(&String)
+                            .and_then(|s| s.str().cloned())
                             .map(|ca| {
                                 ca.into_iter()
                                     .any(|s| s.map(|s| re.is_match(s)).unwrap_or(false))
@@ -149,12 +309,12 @@ fn list_contains(column: &str, pattern: &Expr, elem_type: &DataType) -> Result<P
     }
 }
 
-fn string_contains(column: &str, pattern: &Expr) -> Result<PolarsExpr> {
+fn string_contains(expr: PolarsExpr, column: &str, pattern: &Expr) -> Result<PolarsExpr> {
     if let Expr::String(re) = pattern {
         regex::Regex::new(re)
             .map_err(|_| anyhow!("invalid contains regex '{re}' for column '{column}'"))?;
 
-        Ok(col(column).str().contains(lit(re.to_owned()), false))
+        Ok(expr.str().contains(lit(re.to_owned()), false))
     } else {
         Err(anyhow!(
             "contains predicate for column '{column}' must be a regex"
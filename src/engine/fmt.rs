@@ -4,9 +4,13 @@ use anyhow::Result;
 use comfy_table::presets;
 use comfy_table::{ColumnConstraint, ContentArrangement, Width};
 use comfy_table::{Row, Table};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, terminal};
 use polars::prelude::*;
 use std::{env, io::Write};
 
+use crate::config::OutputFormat;
+
 /// Prints a dataframe in test format, used for test comparisons.
 pub fn df_test(out: &mut dyn Write, df: DataFrame) -> Result<()> {
     env::set_var("POLARS_FMT_TABLE_CELL_LIST_LEN", "6");
@@ -54,17 +58,49 @@ pub fn df_test(out: &mut dyn Write, df: DataFrame) -> Result<()> {
     Ok(())
 }
 
+/// Writes a dataframe in CSV or JSON format.
+pub fn write_df(w: &mut dyn Write, df: &mut DataFrame, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Csv => CsvWriter::new(w).finish(df)?,
+        OutputFormat::Json => {
+            JsonWriter::new(&mut *w)
+                .with_json_format(JsonFormat::Json)
+                .finish(df)?;
+            writeln!(w)?;
+        }
+        OutputFormat::Ndjson => JsonWriter::new(w)
+            .with_json_format(JsonFormat::JsonLines)
+            .finish(df)?,
+        OutputFormat::Table => writeln!(w, "{df}")?,
+    }
+
+    Ok(())
+}
+
+/// Formats a dataframe as a table, with a note if some columns are hidden
+/// because there are more than `max_columns`.
+pub fn table(df: &DataFrame, max_columns: usize) -> String {
+    match hidden_columns_note(df.width(), max_columns) {
+        Some(note) => format!("{df}\n{note}"),
+        None => df.to_string(),
+    }
+}
+
+/// Returns a note with the number of hidden columns, if any.
+fn hidden_columns_note(num_columns: usize, max_columns: usize) -> Option<String> {
+    let hidden = num_columns.saturating_sub(max_columns);
+    match hidden {
+        0 => None,
+        1 => Some("(1 more column hidden, use config(max_columns = ...))".to_string()),
+        n => Some(format!(
+            "({n} more columns hidden, use config(max_columns = ...))"
+        )),
+    }
+}
+
 /// Prints a dataframe in glimpse format.
 pub fn glimpse(w: &mut dyn Write, df: LazyFrame) -> Result<()> {
-    let num_rows = df
-        .clone()
-        .count()
-        .collect()?
-        .max_horizontal()?
-        .unwrap_or_default()
-        .max::<usize>()?
-        .unwrap_or_default();
-
+    let num_rows = count_rows(&df)?;
     let df = df.fetch(100)?;
     let num_cols = df.get_columns().len();
 
@@ -110,6 +146,45 @@ pub fn glimpse(w: &mut dyn Write, df: LazyFrame) -> Result<()> {
     Ok(())
 }
 
+/// Prints a dataframe glimpse as a JSON array with the name, type, and sample
+/// values of each column.
+pub fn glimpse_json(w: &mut dyn Write, df: LazyFrame) -> Result<()> {
+    let df = df.fetch(10)?;
+
+    let columns = df
+        .get_columns()
+        .iter()
+        .map(|col| {
+            let values = (0..col.len())
+                .map(|idx| match col.get(idx) {
+                    Ok(AnyValue::Null) | Err(_) => serde_json::Value::Null,
+                    Ok(_) => col.str_value(idx).unwrap_or_default().into(),
+                })
+                .collect::<Vec<_>>();
+
+            serde_json::json!({
+                "name": col.name(),
+                "dtype": col.dtype().to_string(),
+                "sample": values,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    writeln!(w, "{}", serde_json::Value::Array(columns))?;
+    Ok(())
+}
+
+pub fn count_rows(df: &LazyFrame) -> Result<usize> {
+    Ok(df
+        .clone()
+        .count()
+        .collect()?
+        .max_horizontal()?
+        .unwrap_or_default()
+        .max::<usize>()?
+        .unwrap_or_default())
+}
+
 fn fmt_usize(n: usize) -> String {
     // Colon separated groups of 3.
     let mut s = n.to_string();
@@ -120,3 +195,196 @@ fn fmt_usize(n: usize) -> String {
 
     s
 }
+
+/// Output device used to page long outputs.
+pub trait Pager {
+    /// Returns the number of lines that fit in a screen.
+    fn height(&self) -> usize;
+
+    /// Writes a chunk of lines.
+    fn write_lines(&mut self, lines: &[&str]) -> Result<()>;
+
+    /// Prompts for more output, returns false if the user wants to stop.
+    fn more(&mut self) -> Result<bool>;
+}
+
+/// Writes text a screenful at a time prompting the user between chunks.
+pub fn page(text: &str, pager: &mut dyn Pager) -> Result<()> {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    // Leave a line for the prompt.
+    let chunk_size = pager.height().saturating_sub(1).max(1);
+    let mut chunks = lines.chunks(chunk_size).peekable();
+
+    while let Some(chunk) = chunks.next() {
+        pager.write_lines(chunk)?;
+
+        if chunks.peek().is_some() && !pager.more()? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Pager that writes to the standard output terminal.
+pub struct TerminalPager;
+
+impl Pager for TerminalPager {
+    fn height(&self) -> usize {
+        terminal::size()
+            .map(|(_, rows)| rows as usize)
+            .unwrap_or(24)
+    }
+
+    fn write_lines(&mut self, lines: &[&str]) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        for line in lines {
+            writeln!(stdout, "{line}")?;
+        }
+
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn more(&mut self) -> Result<bool> {
+        let mut stdout = std::io::stdout();
+        write!(stdout, "-- more (Enter/q) --")?;
+        stdout.flush()?;
+
+        terminal::enable_raw_mode()?;
+        let more = read_more_key();
+        terminal::disable_raw_mode()?;
+
+        // Remove the prompt before writing the next chunk.
+        execute!(
+            stdout,
+            cursor::MoveToColumn(0),
+            terminal::Clear(terminal::ClearType::CurrentLine)
+        )?;
+
+        more
+    }
+}
+
+fn read_more_key() -> Result<bool> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Enter | KeyCode::Char(' ') => return Ok(true),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(false)
+                }
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakePager {
+        height: usize,
+        answers: Vec<bool>,
+        chunks: Vec<Vec<String>>,
+        prompts: usize,
+    }
+
+    impl FakePager {
+        fn new(height: usize, answers: &[bool]) -> Self {
+            Self {
+                height,
+                answers: answers.iter().rev().copied().collect(),
+                chunks: Vec::new(),
+                prompts: 0,
+            }
+        }
+    }
+
+    impl Pager for FakePager {
+        fn height(&self) -> usize {
+            self.height
+        }
+
+        fn write_lines(&mut self, lines: &[&str]) -> Result<()> {
+            self.chunks
+                .push(lines.iter().map(|s| s.to_string()).collect());
+            Ok(())
+        }
+
+        fn more(&mut self) -> Result<bool> {
+            self.prompts += 1;
+            Ok(self.answers.pop().unwrap_or(true))
+        }
+    }
+
+    #[test]
+    fn hidden_columns() {
+        assert_eq!(hidden_columns_note(8, 8), None);
+        assert_eq!(hidden_columns_note(3, 8), None);
+        assert_eq!(
+            hidden_columns_note(9, 8).unwrap(),
+            "(1 more column hidden, use config(max_columns = ...))"
+        );
+        assert_eq!(
+            hidden_columns_note(15, 8).unwrap(),
+            "(7 more columns hidden, use config(max_columns = ...))"
+        );
+    }
+
+    fn text(n: usize) -> String {
+        (1..=n).map(|i| format!("line{i}\n")).collect()
+    }
+
+    #[test]
+    fn page_chunks() {
+        let mut pager = FakePager::new(4, &[]);
+        page(&text(7), &mut pager).unwrap();
+
+        assert_eq!(
+            pager.chunks,
+            [
+                vec!["line1", "line2", "line3"],
+                vec!["line4", "line5", "line6"],
+                vec!["line7"],
+            ]
+        );
+        assert_eq!(pager.prompts, 2);
+    }
+
+    #[test]
+    fn page_fits_screen() {
+        let mut pager = FakePager::new(4, &[]);
+        page(&text(3), &mut pager).unwrap();
+
+        assert_eq!(pager.chunks, [vec!["line1", "line2", "line3"]]);
+        assert_eq!(pager.prompts, 0);
+    }
+
+    #[test]
+    fn page_quit() {
+        let mut pager = FakePager::new(3, &[true, false]);
+        page(&text(10), &mut pager).unwrap();
+
+        assert_eq!(
+            pager.chunks,
+            [vec!["line1", "line2"], vec!["line3", "line4"]]
+        );
+        assert_eq!(pager.prompts, 2);
+    }
+
+    #[test]
+    fn page_small_screen() {
+        let mut pager = FakePager::new(0, &[]);
+        page(&text(2), &mut pager).unwrap();
+
+        assert_eq!(pager.chunks, [vec!["line1"], vec!["line2"]]);
+    }
+}
@@ -9,9 +9,23 @@ use super::*;
 /// Evaluates a glimpse call.
 ///
 /// Parameters are checked before evaluation by the typing module.
-pub fn eval(_args: &[Expr], ctx: &mut Context) -> Result<()> {
-    if let Some(df) = ctx.take_df() {
-        ctx.glimpse(df)?;
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    let format = match args::named_string(args, "format").as_deref() {
+        Some("json") => Some(OutputFormat::Json),
+        Some("table") => Some(OutputFormat::Table),
+        Some(format) => bail!("glimpse error: unknown format '{format}', use json or table"),
+        None => None,
+    };
+
+    if let Some(Expr::Identifier(name)) = args.first() {
+        // Peek at a variable without consuming the active dataframe or group.
+        let Some(df) = ctx.get_df(name).cloned() else {
+            bail!("glimpse error: undefined variable '{name}'");
+        };
+
+        ctx.glimpse(df, format)?;
+    } else if let Some(df) = ctx.take_df() {
+        ctx.glimpse(df, format)?;
     } else if ctx.is_grouping() {
         bail!("glimpse error: must call summarize after a group_by");
     } else {
@@ -3,7 +3,7 @@
 use anyhow::{bail, Result};
 use polars::prelude::*;
 
-use crate::parser::Expr;
+use crate::parser::{Expr, Operator};
 
 use super::*;
 
@@ -13,22 +13,41 @@ use super::*;
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(df) = ctx.take_df() {
         let schema_cols = ctx.columns();
-        let mut columns = Vec::new();
 
+        // Report all unknown columns in one go.
+        let names = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Expr::Identifier(column) => Some(column.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        args::check_columns(&names, schema_cols).map_err(|e| error::prefix(e, "group_by"))?;
+
+        let mut group_columns = Vec::new();
         for arg in args {
-            if let Expr::Identifier(column) = arg {
-                if !schema_cols.contains(column) {
-                    bail!("group_by error: Unknown column {column}");
+            let matches = match arg {
+                Expr::Identifier(column) => vec![column.to_owned()],
+                Expr::UnaryOp(Operator::Not, expr) => {
+                    select::filter_columns(expr, schema_cols, true)
                 }
+                _ => select::filter_columns(arg, schema_cols, false),
+            };
+
+            if matches.is_empty() {
+                bail!("group_by error: {arg} doesn't match any column");
+            }
 
-                let expr = col(column);
-                if !columns.contains(&expr) {
-                    columns.push(expr);
+            for column in matches {
+                if !group_columns.contains(&column) {
+                    group_columns.push(column);
                 }
             }
         }
 
-        ctx.set_group(df.group_by_stable(&columns))?;
+        let columns = group_columns.iter().map(|c| col(c)).collect::<Vec<_>>();
+
+        ctx.set_group(df.group_by_stable(&columns), group_columns)?;
     } else {
         bail!("group_by error: missing input dataframe");
     }
@@ -10,14 +10,27 @@ use super::*;
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
-    if let Some(df) = ctx.take_df() {
-        let limit = if !args.is_empty() {
-            args::number(&args[0]) as u32
-        } else {
-            10
+    let (var, args) = match args.first() {
+        Some(Expr::Identifier(name)) => (Some(name), &args[1..]),
+        _ => (None, args),
+    };
+
+    let limit = if !args.is_empty() {
+        args::number(&args[0]) as u32
+    } else {
+        10
+    };
+
+    if let Some(name) = var {
+        // Peek at a variable without consuming the active dataframe or group.
+        let Some(df) = ctx.get_df(name).cloned() else {
+            bail!("head error: undefined variable '{name}'");
         };
 
-        let df = df.limit(limit).collect()?;
+        let df = ctx.collect(df.limit(limit), "head")?;
+        ctx.print(df)?;
+    } else if let Some(df) = ctx.take_df() {
+        let df = ctx.collect(df.limit(limit), "head")?;
         ctx.print(df)?;
     } else if ctx.is_grouping() {
         bail!("head error: must call summarize after a group_by");
@@ -8,18 +8,30 @@ use crate::parser::{Expr, Operator};
 
 use super::*;
 
+/// Maximum size of the cross product used for inequality joins.
+const MAX_CROSS_JOIN_ROWS: usize = 100_000_000;
+
 /// Evaluates a join call.
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()> {
+    eval_join(args, ctx, join_type, "join")
+}
+
+/// Evaluates an anti_filter call, an anti join that reports its own errors.
+pub fn eval_anti_filter(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    eval_join(args, ctx, JoinType::Anti, "anti_filter")
+}
+
+fn eval_join(args: &[Expr], ctx: &mut Context, join_type: JoinType, fname: &str) -> Result<()> {
     if let Some(mut lhs_df) = ctx.take_df() {
         let rhs_df_name = args::identifier(&args[0]);
         if let Some(rhs_df) = ctx.get_df(&rhs_df_name) {
-            let lhs_schema = lhs_df.schema().map_err(|e| anyhow!("join error: {e}"))?;
+            let lhs_schema = lhs_df.schema().map_err(|e| error::prefix(e, fname))?;
             let rhs_schema = rhs_df
                 .clone()
                 .schema()
-                .map_err(|e| anyhow!("join error: {e}"))?;
+                .map_err(|e| error::prefix(e, fname))?;
 
             let lhs_schema_cols = lhs_schema
                 .iter_names()
@@ -31,6 +43,18 @@ pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()>
                 .map(|s| s.to_string())
                 .collect::<HashSet<_>>();
 
+            // Inequality conditions are applied after the join.
+            let mut conditions = Vec::new();
+
+            // outer_join(right_df, id == id, coalesce_keys = false)
+            let coalesce = args::named_bool_opt(args, "coalesce_keys")
+                .map_err(|e| error::prefix(e, fname))?
+                .unwrap_or(true);
+            let args = args
+                .iter()
+                .filter(|arg| !matches!(arg, Expr::BinaryOp(_, Operator::Assign, _)))
+                .collect::<Vec<_>>();
+
             // If no join columns are specified use common columns
             let (lhs_cols, rhs_cols) = if args.len() == 1 {
                 let common_cols = lhs_schema_cols
@@ -38,7 +62,7 @@ pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()>
                     .map(|s| col(s))
                     .collect::<Vec<_>>();
                 if common_cols.is_empty() {
-                    bail!("join error: Missing join columns for '{rhs_df_name}'");
+                    bail!("{fname} error: Missing join columns for '{rhs_df_name}'");
                 }
 
                 (common_cols.clone(), common_cols)
@@ -47,17 +71,25 @@ pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()>
                 let mut rhs_cols = Vec::with_capacity(args.len());
 
                 for arg in args.iter().skip(1) {
-                    if let Expr::BinaryOp(lhs, Operator::Eq, rhs) = arg {
+                    if let Expr::BinaryOp(lhs, op, rhs) = arg {
                         let lhs_col = args::identifier(lhs);
                         if !lhs_schema_cols.contains(&lhs_col) {
-                            bail!("join error: Unknown column '{lhs_col}'");
+                            let err = args::unknown_column(&lhs_col, &lhs_schema_cols);
+                            return Err(error::prefix(err, fname));
                         }
-                        lhs_cols.push(col(&lhs_col));
 
                         let rhs_col = args::identifier(rhs);
                         if !rhs_schema_cols.contains(&rhs_col) {
-                            bail!("join error: Unknown column '{rhs_col}'");
+                            let err = args::unknown_column(&rhs_col, &rhs_schema_cols);
+                            return Err(error::prefix(err, fname));
                         }
+
+                        if !matches!(op, Operator::Eq) {
+                            conditions.push((lhs_col, *op, rhs_col));
+                            continue;
+                        }
+
+                        lhs_cols.push(col(&lhs_col));
                         rhs_cols.push(col(&rhs_col));
 
                         let have_same_type = lhs_schema
@@ -67,7 +99,7 @@ pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()>
                             .unwrap_or(false);
                         if !have_same_type {
                             bail!(
-                                "join error: '{lhs_col}' and '{rhs_col}' don't have the same type"
+                                "{fname} error: '{lhs_col}' and '{rhs_col}' don't have the same type"
                             );
                         }
                     }
@@ -76,16 +108,88 @@ pub fn eval(args: &[Expr], ctx: &mut Context, join_type: JoinType) -> Result<()>
                 (lhs_cols, rhs_cols)
             };
 
-            let join_args = JoinArgs::new(join_type).with_coalesce(JoinCoalesce::CoalesceColumns);
-            ctx.set_df(lhs_df.join(rhs_df.clone(), lhs_cols, rhs_cols, join_args))?;
+            let coalesce = if coalesce {
+                JoinCoalesce::CoalesceColumns
+            } else {
+                JoinCoalesce::KeepColumns
+            };
+            let join_args = JoinArgs::new(join_type).with_coalesce(coalesce);
+
+            let df = if conditions.is_empty() {
+                lhs_df.join(rhs_df.clone(), lhs_cols, rhs_cols, join_args)
+            } else {
+                let mut df = if lhs_cols.is_empty() {
+                    if !fits_cross_join(&lhs_df, rhs_df)? {
+                        bail!(
+                            "{fname} error: inequality join with '{rhs_df_name}' needs more than \
+                            {MAX_CROSS_JOIN_ROWS} rows, add an equality condition"
+                        );
+                    }
+
+                    lhs_df.cross_join(rhs_df.clone(), None)
+                } else {
+                    lhs_df.join(rhs_df.clone(), lhs_cols, rhs_cols, join_args)
+                };
+
+                // Right columns with the same name as a left column get a
+                // suffix unless they are coalesced equality keys.
+                let joined_schema = df.schema().map_err(|e| error::prefix(e, fname))?;
+                let predicate = conditions
+                    .into_iter()
+                    .map(|(lhs_col, op, rhs_col)| {
+                        let suffixed = format!("{rhs_col}_right");
+                        let rhs_col = if lhs_schema_cols.contains(&rhs_col)
+                            && joined_schema.contains(&suffixed)
+                        {
+                            suffixed
+                        } else {
+                            rhs_col
+                        };
+
+                        let (lhs, rhs) = (col(&lhs_col), col(&rhs_col));
+                        match op {
+                            Operator::NotEq => lhs.neq(rhs),
+                            Operator::Lt => lhs.lt(rhs),
+                            Operator::LtEq => lhs.lt_eq(rhs),
+                            Operator::Gt => lhs.gt(rhs),
+                            _ => lhs.gt_eq(rhs),
+                        }
+                    })
+                    .reduce(|acc, e| acc.and(e))
+                    .unwrap();
+
+                df.filter(predicate)
+            };
+
+            ctx.set_df(df)?;
         } else {
-            bail!("join error: undefined dataframe variable '{rhs_df_name}'");
+            bail!("{fname} error: undefined dataframe variable '{rhs_df_name}'");
         }
     } else if ctx.is_grouping() {
-        bail!("join error: must call summarize after a group_by");
+        bail!("{fname} error: must call summarize after a group_by");
     } else {
-        bail!("join error: missing input dataframe");
+        bail!("{fname} error: missing input dataframe");
     }
 
     Ok(())
 }
+
+/// Returns true if the cross product of two dataframes has at most
+/// `MAX_CROSS_JOIN_ROWS` rows.
+///
+/// Rows are counted up to the bound so that large inputs are not computed in
+/// full.
+fn fits_cross_join(lhs_df: &LazyFrame, rhs_df: &LazyFrame) -> Result<bool> {
+    let bounded_rows = |df: &LazyFrame, bound: usize| {
+        let limit = IdxSize::try_from(bound.saturating_add(1)).unwrap_or(IdxSize::MAX);
+        fmt::count_rows(&df.clone().limit(limit))
+    };
+
+    let lhs_rows = bounded_rows(lhs_df, MAX_CROSS_JOIN_ROWS)?;
+    if lhs_rows == 0 {
+        return Ok(true);
+    }
+
+    let rhs_rows = bounded_rows(rhs_df, MAX_CROSS_JOIN_ROWS / lhs_rows)?;
+    Ok(lhs_rows.saturating_mul(rhs_rows) <= MAX_CROSS_JOIN_ROWS)
+}
@@ -1,8 +1,8 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use polars::prelude::*;
-use std::{num::NonZeroUsize, path::PathBuf};
+use std::{io::Cursor, num::NonZeroUsize, path::Path, path::PathBuf};
 
 use crate::parser::Expr;
 
@@ -14,30 +14,96 @@ use super::*;
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     let path = PathBuf::from(args::string(&args[0]));
     let overwrite = args::named_bool(args, "overwrite")?;
+    let root = args::named_string(args, "path");
+    let schema_rows = args::named_usize(args, "schema_rows")?;
 
     // If there is an input dataframe save it to disk.
     if let Some(df) = ctx.take_df() {
+        if root.is_some() || schema_rows.is_some() {
+            bail!("json error: `path` and `schema_rows` can only be used when reading");
+        }
+
         if !overwrite && path.exists() {
-            bail!("json error: file '{}' already exists.", path.display());
+            bail!(Error::io(format!(
+                "json error: file '{}' already exists.",
+                path.display()
+            )));
         }
 
-        let file = std::fs::File::create(&path)
-            .map_err(|e| anyhow!("parquet error: cannot create file '{}' {e}", path.display()))?;
+        let file = std::fs::File::create(&path).map_err(|e| {
+            Error::io(format!(
+                "parquet error: cannot create file '{}' {e}",
+                path.display()
+            ))
+        })?;
 
-        let mut out_df = df.clone().collect()?;
+        let mut out_df = ctx.collect(df.clone(), "json")?;
         ctx.set_df(df)?;
 
         JsonWriter::new(file)
             .with_json_format(JsonFormat::JsonLines)
             .finish(&mut out_df)?;
+    } else if let Some(root) = root {
+        if schema_rows.is_some() {
+            bail!("json error: `schema_rows` cannot be used with `path`");
+        }
+
+        let df = read_path(&path, &root)?;
+        ctx.set_df(df.lazy())?;
     } else {
         // Read the data frame and set it as input for the next task.
         let df = LazyJsonLineReader::new(&path)
-            .with_infer_schema_length(NonZeroUsize::new(1000))
+            .with_infer_schema_length(NonZeroUsize::new(schema_rows.unwrap_or(1000)))
             .finish()
-            .map_err(|e| anyhow!("json error: cannot read file '{}' {e}", path.display()))?;
+            .map_err(|e| {
+                Error::io(format!(
+                    "json error: cannot read file '{}' {e}",
+                    path.display()
+                ))
+            })?;
         ctx.set_df(df)?;
     }
 
     Ok(())
 }
+
+/// Reads the array of objects at a dotted path like `data.rows` in a JSON file.
+fn read_path(path: &Path, root: &str) -> Result<DataFrame> {
+    let read_error = |e: &dyn std::fmt::Display| {
+        Error::io(format!(
+            "json error: cannot read file '{}' {e}",
+            path.display()
+        ))
+    };
+
+    let file = std::fs::File::open(path).map_err(|e| read_error(&e))?;
+    let value: serde_json::Value =
+        serde_json::from_reader(std::io::BufReader::new(file)).map_err(|e| read_error(&e))?;
+
+    let mut rows = &value;
+    for key in root.split('.') {
+        match rows.get(key) {
+            Some(value) => rows = value,
+            None => bail!(
+                "json error: path '{root}' not found in '{}'",
+                path.display()
+            ),
+        }
+    }
+
+    match rows.as_array() {
+        Some(array) if array.iter().all(|row| row.is_object()) => {}
+        _ => bail!(
+            "json error: path '{root}' in '{}' is not an array of objects",
+            path.display()
+        ),
+    }
+
+    let df = JsonReader::new(Cursor::new(serde_json::to_vec(rows)?))
+        .with_json_format(JsonFormat::Json)
+        .infer_schema_len(NonZeroUsize::new(1000))
+        .finish()
+        .map_err(|e| read_error(&e))?;
+
+    Ok(df)
+}
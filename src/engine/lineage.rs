@@ -0,0 +1,124 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+use crate::parser::{Expr, Operator};
+
+use super::*;
+
+/// Columns provenance, maps a column name to the expression that produced it.
+pub type Lineage = HashMap<String, String>;
+
+/// Evaluates a lineage call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(_args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if ctx.take_df().is_some() {
+        let columns = ctx.columns().clone();
+        let provenance = columns
+            .iter()
+            .map(|c| ctx.lineage.get(c).cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        let df = DataFrame::new(vec![
+            Series::new("column", columns),
+            Series::new("provenance", provenance),
+        ])?;
+        ctx.print(df)?;
+    } else if ctx.is_grouping() {
+        bail!("lineage error: must call summarize after a group_by");
+    } else {
+        bail!("lineage error: missing input dataframe");
+    }
+
+    Ok(())
+}
+
+/// Updates the columns provenance after a pipeline step.
+///
+/// Columns assigned by `mutate` and `summarize` are produced by their
+/// expression, columns renamed by `rename` and `select` and the right columns
+/// of a join point to their original column, other columns keep their
+/// provenance and new columns are produced by the step itself. Variables steps
+/// set the provenance when they are evaluated.
+pub fn update(expr: &Expr, prev_columns: &[String], mut prev: Lineage, ctx: &mut Context) {
+    if matches!(expr, Expr::Identifier(_)) {
+        return;
+    }
+
+    let mut lineage = Lineage::new();
+
+    if let Expr::Function(name, args) = expr {
+        match name.as_str() {
+            "mutate" | "summarize" => {
+                for arg in args {
+                    if let Expr::BinaryOp(lhs, Operator::Assign, rhs) = arg {
+                        lineage.insert(args::identifier(lhs), rhs.to_string());
+                    }
+                }
+            }
+            "rename" => {
+                // Renamed columns keep their position.
+                for (old, new) in prev_columns.iter().zip(ctx.columns()) {
+                    if old != new {
+                        lineage.insert(new.clone(), renamed(old, &prev));
+                    }
+                }
+            }
+            "select" => {
+                for arg in args {
+                    if let Expr::BinaryOp(lhs, Operator::Assign, rhs) = arg {
+                        let alias = args::identifier(lhs);
+                        let column = args::identifier(rhs);
+                        if alias != column {
+                            lineage.insert(alias, renamed(&column, &prev));
+                        }
+                    }
+                }
+            }
+            "anti_join" | "cross_join" | "inner_join" | "left_join" | "outer_join" => {
+                let rhs_name = args::identifier(&args[0]);
+                if let Some(rhs_lineage) = ctx.vars_lineage.get(&rhs_name) {
+                    for column in ctx.columns() {
+                        if prev.contains_key(column) {
+                            continue;
+                        }
+
+                        // Right columns with the same name as a left column get a suffix.
+                        let original = match column.strip_suffix("_right") {
+                            Some(original) if !rhs_lineage.contains_key(column) => original,
+                            _ => column.as_str(),
+                        };
+
+                        if let Some(provenance) = rhs_lineage.get(original) {
+                            let provenance = format!("{rhs_name}.{original} <- {provenance}");
+                            lineage.insert(column.clone(), provenance);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ctx.lineage = ctx
+        .columns()
+        .iter()
+        .map(|column| {
+            let provenance = lineage
+                .remove(column)
+                .or_else(|| prev.remove(column))
+                .unwrap_or_else(|| expr.to_string());
+            (column.clone(), provenance)
+        })
+        .collect();
+}
+
+fn renamed(column: &str, lineage: &Lineage) -> String {
+    match lineage.get(column) {
+        Some(provenance) => format!("{column} <- {provenance}"),
+        None => column.to_string(),
+    }
+}
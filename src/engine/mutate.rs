@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use anyhow::{bail, Result};
+use polars::chunked_array::temporal::validate_time_zone;
 use polars::lazy::dsl::{duration, DurationArgs, Expr as PolarsExpr, StrptimeOptions};
 use polars::prelude::*;
 use std::collections::HashSet;
@@ -25,33 +26,18 @@ use super::*;
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
-    if let Some(mut df) = ctx.take_df() {
-        let mut used_aliases = HashSet::new();
-
-        for arg in args {
-            match arg {
-                Expr::BinaryOp(lhs, Operator::Assign, rhs) => {
-                    let alias = args::identifier(lhs);
-                    if used_aliases.contains(&alias) {
-                        bail!("mutate error: duplicate alias '{alias}'");
-                    } else {
-                        used_aliases.insert(alias.clone());
-                    }
-
-                    let expr = df
-                        .schema()
-                        .map_err(anyhow::Error::from)
-                        .and_then(|schema| eval_expr(rhs, &schema))
-                        .map_err(|e| anyhow!("mutate error: {e}"))?;
-                    df = df.with_column(expr.alias(&alias));
-                }
-                _ => panic!("Unexpected mutate expression: {arg}"),
-            }
-        }
-
+    if let Some(df) = ctx.take_df() {
+        let df = mutate(args, df, Scope::new(&[], ctx.strict))?;
+        ctx.set_df(df)?;
+    } else if let Some(group) = ctx.take_group() {
+        // Evaluate window functions within each group, the result is not grouped.
+        let keys = ctx
+            .group_columns()
+            .iter()
+            .map(|c| col(c))
+            .collect::<Vec<_>>();
+        let df = mutate(args, group.into(), Scope::new(&keys, ctx.strict))?;
         ctx.set_df(df)?;
-    } else if ctx.is_grouping() {
-        bail!("mutate error: must call summarize after a group_by");
     } else {
         bail!("mutate error: missing input dataframe");
     }
@@ -59,34 +45,120 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
-fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+/// Functions that are computed within groups after a `group_by`.
+const GROUP_FUNCTIONS: [&str; 7] = [
+    "fill_group",
+    "fill_null",
+    "ntile",
+    "rolling_max",
+    "rolling_mean",
+    "rolling_min",
+    "rolling_sum",
+];
+
+/// Options shared by the expressions of a mutate call.
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    /// Keys of the active group, empty if there is no group.
+    keys: &'a [PolarsExpr],
+    /// Fail on invalid conversions instead of setting nulls.
+    strict: bool,
+}
+
+impl<'a> Scope<'a> {
+    fn new(keys: &'a [PolarsExpr], strict: bool) -> Self {
+        Self { keys, strict }
+    }
+
+    /// Evaluates a window expression within each group if there is a group.
+    fn over(&self, expr: PolarsExpr) -> PolarsExpr {
+        if self.keys.is_empty() {
+            expr
+        } else {
+            expr.over(self.keys)
+        }
+    }
+}
+
+fn mutate(args: &[Expr], mut df: LazyFrame, scope: Scope<'_>) -> Result<LazyFrame> {
+    let mut used_aliases = HashSet::new();
+
+    for arg in args {
+        match arg {
+            Expr::BinaryOp(lhs, Operator::Assign, rhs) => {
+                let alias = args::identifier(lhs);
+                if used_aliases.contains(&alias) {
+                    bail!("mutate error: duplicate alias '{alias}'");
+                } else {
+                    used_aliases.insert(alias.clone());
+                }
+
+                if scope.keys.is_empty() && uses_function(rhs, "fill_group") {
+                    bail!("mutate error: `fill_group` must be called after a group_by");
+                }
+
+                if !scope.keys.is_empty() && !GROUP_FUNCTIONS.iter().any(|f| uses_function(rhs, f))
+                {
+                    bail!(
+                        "mutate error: '{alias}' must use one of {} after a group_by",
+                        GROUP_FUNCTIONS.join(", ")
+                    );
+                }
+
+                let expr = df
+                    .schema()
+                    .map_err(anyhow::Error::from)
+                    .and_then(|schema| eval_expr(rhs, &schema, scope))
+                    .map_err(|e| error::prefix(e, "mutate"))?;
+
+                df = df.with_column(expr.alias(&alias));
+            }
+            _ => panic!("Unexpected mutate expression: {arg}"),
+        }
+    }
+
+    Ok(df)
+}
+
+fn eval_expr(expr: &Expr, schema: &Schema, scope: Scope<'_>) -> Result<PolarsExpr> {
     match expr {
-        Expr::BinaryOp(lhs, op, rhs) => {
-            let lhs = eval_expr(lhs, schema)?;
-            let rhs = eval_expr(rhs, schema)?;
-
-            let result = match op {
-                Operator::Plus => lhs + rhs,
-                Operator::Minus => lhs - rhs,
-                Operator::Multiply => lhs * rhs,
-                Operator::Divide => lhs / rhs,
-                Operator::Mod => lhs % rhs.cast(DataType::UInt64),
-                _ => panic!("Unexpected mutate operator {op}"),
-            };
+        Expr::BinaryOp(lhs_expr, op, rhs_expr) => {
+            let lhs = eval_expr(lhs_expr, schema, scope)?;
+            let rhs = eval_expr(rhs_expr, schema, scope)?;
 
-            Ok(result)
+            match op {
+                Operator::Eq => return Ok(lhs.eq(rhs)),
+                Operator::NotEq => return Ok(lhs.neq(rhs)),
+                Operator::Lt => return Ok(lhs.lt(rhs)),
+                Operator::LtEq => return Ok(lhs.lt_eq(rhs)),
+                Operator::Gt => return Ok(lhs.gt(rhs)),
+                Operator::GtEq => return Ok(lhs.gt_eq(rhs)),
+                Operator::And => return Ok(lhs.and(rhs)),
+                Operator::Or => return Ok(lhs.or(rhs)),
+                _ => {}
+            }
+
+            arith(lhs, op, rhs, (lhs_expr, rhs_expr), schema)
         }
-        Expr::Identifier(_) => args::column(expr, schema),
+        Expr::Identifier(_) => args::column_or_bool(expr, schema),
         Expr::String(s) => Ok(lit(s.clone())),
         Expr::Number(n) => Ok(lit(*n)),
         Expr::Function(name, args) if name == "ymd_hms" => {
+            // Strings that don't parse are an error, in strict mode they are
+            // checked after the conversion to report the value and its row.
+            let options = StrptimeOptions {
+                strict: !scope.strict,
+                ..Default::default()
+            };
+
             args::column(&args[0], schema).map(|c| {
-                c.str().to_datetime(
+                let ts = c.clone().str().to_datetime(
                     Some(TimeUnit::Nanoseconds),
                     None,
-                    StrptimeOptions::default(),
+                    options,
                     lit("raise"),
-                )
+                );
+                check_conversion(c, ts, "ymd_hms", scope.strict)
             })
         }
         Expr::Function(name, args) if name == "dnanos" => args::column(&args[0], schema).map(|c| {
@@ -129,6 +201,402 @@ fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
         Expr::Function(name, args) if name == "secs" => {
             args::column(&args[0], schema).map(|c| c.dt().total_seconds())
         }
+        Expr::Function(name, args) if name == "minutes" => {
+            args::column(&args[0], schema).map(|c| c.dt().total_minutes())
+        }
+        Expr::Function(name, args) if name == "hours" => {
+            args::column(&args[0], schema).map(|c| c.dt().total_hours())
+        }
+        Expr::Function(name, args) if name == "days" => {
+            args::column(&args[0], schema).map(|c| c.dt().total_days())
+        }
+        Expr::Function(name, args) if name == "epoch_seconds" => args::column(&args[0], schema)
+            .map(|c| c.dt().timestamp(TimeUnit::Milliseconds) / lit(1000i64)),
+        Expr::Function(name, args) if name == "from_epoch" => {
+            // Seconds can be fractional, convert to nanoseconds before casting.
+            args::column(&args[0], schema).map(|c| {
+                let ts = (c.clone().cast(DataType::Float64) * lit(1e9))
+                    .cast(DataType::Int64)
+                    .cast(DataType::Datetime(TimeUnit::Nanoseconds, None));
+                check_conversion(c, ts, name, scope.strict)
+            })
+        }
+        // `convert_tz` is an alias of `timezone_convert`.
+        Expr::Function(name, args) if name == "timezone_convert" || name == "convert_tz" => {
+            let column = args::identifier(&args[0]);
+            let from_tz = time_zone(name, &args[1])?;
+            let to_tz = time_zone(name, &args[2])?;
+
+            match schema.get(&column) {
+                Some(DataType::Datetime(_, None)) => {
+                    let ts = col(&column)
+                        .dt()
+                        .replace_time_zone(Some(from_tz), lit("earliest"), NonExistent::Null)
+                        .dt()
+                        .convert_time_zone(to_tz);
+                    Ok(check_conversion(col(&column), ts, name, scope.strict))
+                }
+                // The time zone of an aware column must match the source one.
+                Some(DataType::Datetime(_, Some(tz))) if *tz == from_tz => {
+                    Ok(col(&column).dt().convert_time_zone(to_tz))
+                }
+                Some(DataType::Datetime(_, Some(tz))) => Err(anyhow!(
+                    "`{name}` column '{column}' has time zone '{tz}', not '{from_tz}'"
+                )),
+                Some(_) => Err(anyhow!("`{name}` column '{column}' must be a datetime")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "set_tz" => {
+            // Sets the time zone keeping the local time.
+            let column = args::identifier(&args[0]);
+            let tz = time_zone(name, &args[1])?;
+
+            match schema.get(&column) {
+                Some(DataType::Datetime(_, _)) => {
+                    let ts = col(&column).dt().replace_time_zone(
+                        Some(tz),
+                        lit("earliest"),
+                        NonExistent::Null,
+                    );
+                    Ok(check_conversion(col(&column), ts, name, scope.strict))
+                }
+                Some(_) => Err(anyhow!("`{name}` column '{column}' must be a datetime")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "ntile" => {
+            // Assigns each row to a bucket from 1 to n with near equal counts.
+            let buckets = args::number(&args[1]);
+            if buckets < 1.0 || buckets.fract() != 0.0 {
+                bail!("`ntile` number of buckets must be a positive integer");
+            }
+
+            args::column(&args[0], schema).map(|c| {
+                let rank_opts = RankOptions {
+                    method: RankMethod::Ordinal,
+                    descending: false,
+                };
+
+                let rank = scope.over(c.clone().rank(rank_opts, None).cast(DataType::Int64));
+                let count = scope.over(c.count().cast(DataType::Int64));
+                (rank - lit(1i64)) * lit(buckets as i64) / count + lit(1i64)
+            })
+        }
+        Expr::Function(name, args) if name == "parse_int" => {
+            // Strings that don't parse are set to null.
+            let column = args::identifier(&args[0]);
+            let expr = match schema.get(&column) {
+                Some(DataType::String) => col(&column)
+                    .str()
+                    .strip_chars(lit(Null {}))
+                    .str()
+                    .to_integer(lit(10), false),
+                Some(_) => col(&column).cast(DataType::Int64),
+                None => return Err(args::unknown_column(&column, schema.iter_names())),
+            };
+
+            Ok(check_conversion(
+                col(&column),
+                expr,
+                "parse_int",
+                scope.strict,
+            ))
+        }
+        Expr::Function(name, args) if name == "parse_float" => {
+            // Strings that don't parse are set to null.
+            let column = args::identifier(&args[0]);
+            let expr = match schema.get(&column) {
+                Some(DataType::String) => col(&column)
+                    .str()
+                    .strip_chars(lit(Null {}))
+                    .cast(DataType::Float64),
+                Some(_) => col(&column).cast(DataType::Float64),
+                None => return Err(args::unknown_column(&column, schema.iter_names())),
+            };
+
+            Ok(check_conversion(
+                col(&column),
+                expr,
+                "parse_float",
+                scope.strict,
+            ))
+        }
+        Expr::Function(name, args) if name == "normalize" => {
+            // Min-max scaling to [0, 1] or to the [low, high] range.
+            let (low, high) = match args.len() {
+                1 => (0.0, 1.0),
+                3 => (args::number(&args[1]), args::number(&args[2])),
+                _ => bail!("`normalize` expects a column and an optional low and high range"),
+            };
+
+            args::column(&args[0], schema).map(|c| {
+                let c = c.cast(DataType::Float64);
+                let min = c.clone().min();
+                let max = c.clone().max();
+                lit(low) + (c - min.clone()) / (max - min) * lit(high - low)
+            })
+        }
+        Expr::Function(name, args) if name == "standardize" => {
+            // Z-score using the sample standard deviation.
+            args::column(&args[0], schema).map(|c| {
+                let c = c.cast(DataType::Float64);
+                (c.clone() - c.clone().mean()) / c.std(1)
+            })
+        }
+        Expr::Function(name, args) if name == "fill_null" => {
+            // fill_null(column, strategy = "mean")
+            let column = args::identifier(&args[0]);
+            let Some(dtype) = schema.get(&column) else {
+                return Err(args::unknown_column(&column, schema.iter_names()));
+            };
+
+            let strategy = args::named_string(args, "strategy").unwrap_or_default();
+            if strategy != "min" && strategy != "max" && !dtype.is_numeric() {
+                bail!("`fill_null` column '{column}' must be numeric for strategy '{strategy}', found {dtype}");
+            }
+
+            let c = col(&column);
+            let value = match strategy.as_str() {
+                "mean" => c.clone().mean(),
+                "median" => c.clone().median(),
+                "min" => c.clone().min(),
+                "max" => c.clone().max(),
+                "zero" => lit(0).cast(dtype.clone()),
+                _ => bail!(
+                    "`fill_null` unknown strategy '{strategy}', expected 'mean', 'median', 'min', 'max' or 'zero'"
+                ),
+            };
+
+            Ok(c.fill_null(scope.over(value)))
+        }
+        Expr::Function(name, args) if name == "fill_group" => {
+            // Forward fill within each group.
+            args::column(&args[0], schema).map(|c| scope.over(c.forward_fill(None)))
+        }
+        Expr::Function(name, args) if name == "cut" => {
+            // cut(column, [break1, break2, ...], labels = [label1, label2, ...])
+            let breaks = args::numbers(&args[1]);
+            if breaks.len() < 2 {
+                bail!("`cut` needs at least two breaks");
+            }
+
+            if breaks.windows(2).any(|w| w[0] >= w[1]) {
+                bail!("`cut` breaks must be in increasing order");
+            }
+
+            let labels = args::named_strings(args, "labels");
+            if let Some(labels) = &labels {
+                if labels.len() != breaks.len() + 1 {
+                    bail!(
+                        "`cut` expected {} labels for {} breaks, got {}",
+                        breaks.len() + 1,
+                        breaks.len(),
+                        labels.len()
+                    );
+                }
+            }
+
+            args::column(&args[0], schema).map(|c| c.cut(breaks, labels, true, false))
+        }
+        Expr::Function(name, args) if name == "qcut" => {
+            // qcut(column, n, labels = [label1, label2, ...])
+            let bins = args::number(&args[1]);
+            if bins < 1.0 || bins.fract() != 0.0 {
+                bail!("`qcut` number of quantiles must be a positive integer");
+            }
+
+            let labels = args::named_strings(args, "labels");
+            if let Some(labels) = &labels {
+                if labels.len() != bins as usize {
+                    bail!(
+                        "`qcut` expected {} labels, got {}",
+                        bins as usize,
+                        labels.len()
+                    );
+                }
+            }
+
+            args::column(&args[0], schema)
+                .map(|c| c.qcut_uniform(bins as usize, labels, true, true, false))
+        }
+        Expr::Function(name, args) if name == "bin" => {
+            // Like cut but values outside the breaks range are set to null.
+            let breaks = args[1..].iter().map(args::number).collect::<Vec<_>>();
+            if breaks.windows(2).any(|w| w[0] >= w[1]) {
+                bail!("`bin` breaks must be in increasing order");
+            }
+
+            let low = breaks[0];
+            let high = breaks[breaks.len() - 1];
+
+            args::column(&args[0], schema).map(|c| {
+                when(c.clone().gt_eq(lit(low)).and(c.clone().lt(lit(high))))
+                    .then(c)
+                    .otherwise(lit(Null {}))
+                    .cut(breaks, None, true, false)
+            })
+        }
+        Expr::Function(name, args) if name == "concat_str" => {
+            // concat_str(a, b, separator = "-")
+            let separator = args::named_string(args, "separator").unwrap_or_default();
+            let exprs = args
+                .iter()
+                .filter(|arg| matches!(arg, Expr::Identifier(_)))
+                .map(|arg| args::column(arg, schema).map(|c| c.cast(DataType::String)))
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(concat_str(exprs, &separator, false))
+        }
+        Expr::Function(name, args) if name == "format_string" => {
+            let exprs = parse_template(&args::string(&args[0]))?
+                .into_iter()
+                .map(|part| match part {
+                    TemplatePart::Text(text) => Ok(lit(text)),
+                    TemplatePart::Column(column) => schema
+                        .get(&column)
+                        .map(|_| col(&column).cast(DataType::String))
+                        .ok_or_else(|| args::unknown_column(&column, schema.iter_names())),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(concat_str(exprs, "", false))
+        }
+        Expr::Function(name, args) if name == "str_c" => {
+            // str_c(a, " / ", b, sep = "", ignore_null = false)
+            let sep = args::named_string(args, "sep").unwrap_or_default();
+            let ignore_null = args::named_bool(args, "ignore_null")?;
+
+            let exprs = args
+                .iter()
+                .filter_map(|arg| match arg {
+                    Expr::Identifier(_) => {
+                        Some(args::column(arg, schema).map(|c| c.cast(DataType::String)))
+                    }
+                    Expr::String(s) => Some(Ok(lit(s.clone()))),
+                    _ => None,
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            if exprs.is_empty() {
+                bail!("`str_c` expects at least one column or string");
+            }
+
+            Ok(concat_str(exprs, &sep, ignore_null))
+        }
+        Expr::Function(name, args) if name == "coalesce" => {
+            // coalesce(a, b, starts_with("val_"), 0)
+            let schema_cols = schema
+                .iter_names()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>();
+            let mut exprs = Vec::with_capacity(args.len());
+            for arg in args {
+                match arg {
+                    Expr::Function(_, _) => {
+                        let columns = select::filter_columns(arg, &schema_cols, false);
+                        if columns.is_empty() {
+                            bail!("`coalesce` selector {arg} doesn't match any column");
+                        }
+
+                        exprs.extend(columns.iter().map(|c| col(c)));
+                    }
+                    Expr::Identifier(_) => exprs.push(args::column(arg, schema)?),
+                    Expr::String(s) => exprs.push(lit(s.clone())),
+                    Expr::Number(n) => exprs.push(lit(*n)),
+                    _ => {}
+                }
+            }
+
+            Ok(coalesce(&exprs))
+        }
+        Expr::Function(name, args) if name == "str_pad" => {
+            // str_pad(column, width, fill = " ", side = "left")
+            if args.len() > 4 {
+                bail!("`str_pad` expects a column, a width, a fill and a side");
+            }
+
+            let width = args::number(&args[1]);
+            if width < 0.0 || width.fract() != 0.0 {
+                bail!("`str_pad` width must be a non negative integer");
+            }
+
+            let fill = args
+                .get(2)
+                .map(args::string)
+                .unwrap_or_else(|| " ".to_string());
+            let mut chars = fill.chars();
+            let fill = match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => bail!("`str_pad` fill must be a single character, got '{fill}'"),
+            };
+
+            let left = match args.get(3).map(args::string).as_deref() {
+                None | Some("left") => true,
+                Some("right") => false,
+                Some(side) => bail!("`str_pad` side must be 'left' or 'right', got '{side}'"),
+            };
+
+            args::column(&args[0], schema).map(|c| {
+                let c = c.cast(DataType::String).str();
+                if left {
+                    c.pad_start(width as usize, fill)
+                } else {
+                    c.pad_end(width as usize, fill)
+                }
+            })
+        }
+        Expr::Function(name, args) if name == "format_num" => {
+            // Formats numbers with a fixed number of decimals.
+            let decimals = args::number(&args[1]);
+            if decimals < 0.0 || decimals.fract() != 0.0 {
+                bail!("`format_num` decimals must be a non negative integer");
+            }
+
+            let decimals = decimals as usize;
+            args::column(&args[0], schema).map(|c| {
+                c.cast(DataType::Float64).map(
+                    move |s| {
+                        let ca = s
+                            .f64()?
+                            .into_iter()
+                            .map(|v| v.map(|v| format!("{v:.decimals$}")))
+                            .collect::<StringChunked>();
+                        Ok(Some(ca.into_series()))
+                    },
+                    GetOutput::from_type(DataType::String),
+                )
+            })
+        }
+        Expr::Function(name, args) if name == "hash" => {
+            let seed = args::named_usize(args, "seed")?.unwrap_or_default() as u64;
+            let hasher: fn(&[u8], u64) -> u64 =
+                match args::named_string(args, "algorithm").as_deref() {
+                    None => {
+                        return args::column(&args[0], schema).map(|c| c.hash(seed, 0, 0, 0));
+                    }
+                    Some("xxh64") => xxhash_rust::xxh64::xxh64,
+                    Some("fnv") => fnv1a,
+                    Some(algorithm) => {
+                        bail!("`hash` unknown algorithm '{algorithm}', expected 'xxh64' or 'fnv'")
+                    }
+                };
+
+            // Values are hashed using their string representation.
+            args::column(&args[0], schema).map(|c| {
+                c.cast(DataType::String).map(
+                    move |s| {
+                        let ca = s
+                            .str()?
+                            .into_iter()
+                            .map(|v| v.map(|v| hasher(v.as_bytes(), seed)))
+                            .collect::<UInt64Chunked>();
+                        Ok(Some(ca.into_series()))
+                    },
+                    GetOutput::from_type(DataType::UInt64),
+                )
+            })
+        }
         Expr::Function(name, args) if name == "field" => {
             let field_name = args::identifier(&args[1]);
             args::column(&args[0], schema).map(|c| c.struct_().field_by_name(&field_name))
@@ -145,24 +613,335 @@ fn eval_expr(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
         Expr::Function(name, args) if name == "max" => {
             args::column(&args[0], schema).map(|c| c.max())
         }
-        Expr::Function(name, args) if name == "len" => {
+        Expr::Function(name, args) if name == "len" => len(&args[0], schema),
+        Expr::Function(name, args)
+            if matches!(
+                name.as_str(),
+                "rolling_mean" | "rolling_sum" | "rolling_min" | "rolling_max"
+            ) =>
+        {
+            let window_size = args::number(&args[1]);
+            if window_size < 1.0 || window_size.fract() != 0.0 {
+                bail!("`{name}` window size must be a positive integer");
+            }
+
+            // Windows with less than min_periods values are set to null.
+            let window_size = window_size as usize;
+            let min_periods = args::named_usize(args, "min_periods")?.unwrap_or(window_size);
+            let options = RollingOptionsFixedWindow {
+                window_size,
+                min_periods,
+                ..Default::default()
+            };
+
+            let column = args::column(&args[0], schema)?;
+            let expr = match name.as_str() {
+                "rolling_mean" => column.rolling_mean(options),
+                "rolling_sum" => column.rolling_sum(options),
+                "rolling_min" => column.rolling_min(options),
+                _ => column.rolling_max(options),
+            };
+
+            Ok(scope.over(expr))
+        }
+        Expr::Function(name, args)
+            if matches!(
+                name.as_str(),
+                "list_max" | "list_min" | "list_sum" | "list_mean"
+            ) =>
+        {
+            let column = args::identifier(&args[0]);
+            let list = match schema.get(&column) {
+                Some(DataType::List(_)) => col(&column).list(),
+                Some(_) => bail!("`{name}` column '{column}' must be a list"),
+                None => return Err(args::unknown_column(&column, schema.iter_names())),
+            };
+
+            let expr = match name.as_str() {
+                "list_max" => list.max(),
+                "list_min" => list.min(),
+                "list_sum" => list.sum(),
+                _ => list.mean(),
+            };
+
+            Ok(expr)
+        }
+        Expr::Function(name, args) if name == "list_concat" => {
+            let lhs = args::identifier(&args[0]);
+            let rhs = args::identifier(&args[1]);
+
+            for column in [&lhs, &rhs] {
+                match schema.get(column) {
+                    Some(DataType::List(_)) => {}
+                    Some(_) => bail!("`list_concat` column '{column}' must be a list"),
+                    None => return Err(args::unknown_column(column, schema.iter_names())),
+                }
+            }
+
+            Ok(concat_list([col(&lhs), col(&rhs)])?)
+        }
+        Expr::Function(name, args) if name == "list_distinct" => {
             let column = args::identifier(&args[0]);
             match schema.get(&column) {
-                Some(DataType::List(_)) => Ok(col(&column).list().len()),
-                Some(DataType::String) => Ok(col(&column).str().len_chars()),
-                Some(_) => Err(anyhow!("`len` column '{column}' must be list or String")),
-                None => Err(anyhow!("Unknown column '{column}'")),
+                Some(DataType::List(_)) => Ok(col(&column).list().unique_stable()),
+                Some(_) => Err(anyhow!("`list_distinct` column '{column}' must be a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "list_get" => {
+            let column = args::identifier(&args[0]);
+            let index = args::number(&args[1]);
+            if index.fract() != 0.0 {
+                bail!("`list_get` index must be an integer, got {index}");
+            }
+
+            // Negative indexes start from the end, out of bounds values are null.
+            match schema.get(&column) {
+                Some(DataType::List(_)) => Ok(col(&column).list().get(lit(index as i64), true)),
+                Some(_) => Err(anyhow!("`list_get` column '{column}' must be a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "list_join" => {
+            let column = args::identifier(&args[0]);
+            let separator = args::string(&args[1]);
+
+            match schema.get(&column) {
+                // Elements that are not strings are joined by their string value.
+                Some(DataType::List(_)) => Ok(col(&column)
+                    .cast(DataType::List(Box::new(DataType::String)))
+                    .list()
+                    .join(lit(separator), true)),
+                Some(_) => Err(anyhow!("`list_join` column '{column}' must be a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "list_slice" => {
+            let column = args::identifier(&args[0]);
+            let offset = args::number(&args[1]) as i64;
+            let length = args::number(&args[2]);
+            if length < 0.0 {
+                bail!("`list_slice` length must not be negative, got {length}");
+            }
+
+            match schema.get(&column) {
+                Some(DataType::List(_)) => {
+                    Ok(col(&column).list().slice(lit(offset), lit(length as u64)))
+                }
+                Some(_) => Err(anyhow!("`list_slice` column '{column}' must be a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
+            }
+        }
+        Expr::Function(name, args) if name == "list_sort" => {
+            let column = args::identifier(&args[0]);
+            let options = SortOptions {
+                descending: args::named_bool(&args[1..], "descending")?,
+                ..Default::default()
+            };
+
+            match schema.get(&column) {
+                Some(DataType::List(_)) => Ok(col(&column).list().sort(options)),
+                Some(_) => Err(anyhow!("`list_sort` column '{column}' must be a list")),
+                None => Err(args::unknown_column(&column, schema.iter_names())),
             }
         }
         Expr::Function(name, _args) if name == "row" => {
-            let (col_name, _) = schema
-                .get_at_index(0)
-                .ok_or_else(|| anyhow!("No columns found"))?;
-            Ok(col(col_name).map(
-                |s| Ok(Some(Series::from_iter(1..=(s.len() as u64)))),
-                GetOutput::from_type(DataType::UInt64),
-            ))
+            // One based row numbers, the range length comes from the frame so
+            // that it doesn't depend on other selected columns.
+            let end = polars::prelude::len().cast(DataType::UInt64) + lit(1u64);
+            Ok(int_range(lit(1u64), end, 1, DataType::UInt64))
         }
         _ => panic!("Unexpected mutate expression {expr}"),
     }
 }
+
+/// Returns true if the expression calls the function `name`.
+fn uses_function(expr: &Expr, name: &str) -> bool {
+    match expr {
+        Expr::Function(fname, args) => {
+            fname == name || args.iter().any(|arg| uses_function(arg, name))
+        }
+        Expr::BinaryOp(lhs, _, rhs) => uses_function(lhs, name) || uses_function(rhs, name),
+        Expr::UnaryOp(_, expr) => uses_function(expr, name),
+        Expr::List(exprs) => exprs.iter().any(|expr| uses_function(expr, name)),
+        _ => false,
+    }
+}
+
+/// Returns a validated time zone name.
+fn time_zone(fname: &str, expr: &Expr) -> Result<String> {
+    let tz = args::string(expr);
+    validate_time_zone(&tz).map_err(|_| anyhow!("`{fname}` unknown time zone '{tz}'"))?;
+    Ok(tz)
+}
+
+/// Returns the length of a list or string column.
+pub fn len(expr: &Expr, schema: &Schema) -> Result<PolarsExpr> {
+    let column = args::identifier(expr);
+    match schema.get(&column) {
+        Some(DataType::List(_)) => Ok(col(&column).list().len()),
+        Some(DataType::String) => Ok(col(&column).str().len_chars()),
+        Some(_) => Err(anyhow!("`len` column '{column}' must be list or String")),
+        None => Err(args::unknown_column(&column, schema.iter_names())),
+    }
+}
+
+/// Returns a conversion expression that in strict mode fails on the first
+/// non null input value that has been converted to null.
+///
+/// Row numbers are computed with the input so that they are the dataframe
+/// rows even if the conversion is evaluated in batches.
+fn check_conversion(
+    input: PolarsExpr,
+    output: PolarsExpr,
+    fname: &str,
+    strict: bool,
+) -> PolarsExpr {
+    if !strict {
+        return output;
+    }
+
+    let fname = fname.to_string();
+    let end = polars::prelude::len().cast(DataType::UInt64) + lit(1u64);
+    let rows = int_range(lit(1u64), end, 1, DataType::UInt64);
+
+    map_multiple(
+        move |series: &mut [Series]| {
+            let invalid = series[0].is_not_null() & series[1].is_null();
+            if let Some(idx) = invalid.into_iter().position(|v| v == Some(true)) {
+                let value = series[0].get(idx)?;
+                let row = series[2].get(idx)?;
+                polars_bail!(
+                    ComputeError: "`{fname}` cannot convert value {value} in row {row}"
+                );
+            }
+
+            Ok(Some(std::mem::take(&mut series[1])))
+        },
+        [input, output, rows],
+        GetOutput::map_fields(|fields| Ok(fields[1].clone())),
+    )
+}
+
+/// Applies an arithmetic operator to two expressions.
+///
+/// Numeric operands of different types are cast to their common type by
+/// Polars, `lhs_expr` and `rhs_expr` are the parsed operands used to check
+/// their types. A number literal without a fractional part is an integer for
+/// modulo and takes the type of the other operand.
+pub fn arith(
+    lhs: PolarsExpr,
+    op: &Operator,
+    rhs: PolarsExpr,
+    (lhs_expr, rhs_expr): (&Expr, &Expr),
+    schema: &Schema,
+) -> Result<PolarsExpr> {
+    check_operands(lhs_expr, op, rhs_expr, schema)?;
+
+    let rhs = match (op, rhs_expr) {
+        (Operator::Mod, Expr::Number(n)) if *n == 0.0 => {
+            bail!("`%` divisor cannot be zero")
+        }
+        (Operator::Mod, Expr::Number(n)) if n.fract() == 0.0 => {
+            PolarsExpr::Literal(LiteralValue::Int(*n as i128))
+        }
+        _ => rhs,
+    };
+
+    let result = match op {
+        Operator::Plus => lhs + rhs,
+        Operator::Minus => lhs - rhs,
+        Operator::Multiply => lhs * rhs,
+        Operator::Divide => lhs / rhs,
+        Operator::Mod => lhs % rhs,
+        _ => panic!("Unexpected arithmetic operator {op}"),
+    };
+
+    Ok(result)
+}
+
+/// Returns an error if a string column is combined with a number.
+fn check_operands(lhs: &Expr, op: &Operator, rhs: &Expr, schema: &Schema) -> Result<()> {
+    // Number literals are numeric, the type of other expressions is not known.
+    let operand_type = |expr: &Expr| match expr {
+        Expr::Identifier(name) => schema.get(name).cloned(),
+        Expr::Number(_) => Some(DataType::Float64),
+        _ => None,
+    };
+
+    if let (Some(lt), Some(rt)) = (operand_type(lhs), operand_type(rhs)) {
+        if (lt.is_numeric() && rt == DataType::String)
+            || (lt == DataType::String && rt.is_numeric())
+        {
+            bail!(
+                "`{op}` cannot combine {lt} and {rt} values, convert them with parse_int, \
+                parse_float, or format_num first"
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// A part of a `format_string` template.
+enum TemplatePart {
+    Text(String),
+    Column(String),
+}
+
+/// Splits a template like `"{payment_type}: ${total_amount}"` into text and
+/// columns, use `{{` and `}}` for literal braces.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart>> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut column = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => column.push(c),
+                        None => bail!("`format_string` missing closing brace in '{template}'"),
+                    }
+                }
+
+                let column = column.trim();
+                if column.is_empty() {
+                    bail!("`format_string` empty column name in '{template}'");
+                }
+
+                if !text.is_empty() {
+                    parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                }
+                parts.push(TemplatePart::Column(column.to_string()));
+            }
+            '}' => bail!("`format_string` unmatched closing brace in '{template}'"),
+            c => text.push(c),
+        }
+    }
+
+    if !text.is_empty() || parts.is_empty() {
+        parts.push(TemplatePart::Text(text));
+    }
+
+    Ok(parts)
+}
+
+/// Returns the 64 bits FNV-1a hash of the given bytes, the seed is mixed
+/// into the offset basis.
+fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325 ^ seed, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,8 +1,7 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
-use anyhow::{anyhow, bail, Result};
+use anyhow::{bail, Result};
 use polars::prelude::*;
-use std::path::PathBuf;
 
 use crate::parser::Expr;
 
@@ -12,28 +11,72 @@ use super::*;
 ///
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
-    // parquet("nyctaxi.parquet")
-    let path = PathBuf::from(args::string(&args[0]));
+    // parquet("nyctaxi.parquet") or parquet(["jan.parquet", "feb.parquet"])
+    let paths = args::paths(&args[0]);
     // parquet("nyctaxi.parquet", overwrite = true)
     let overwrite = args::named_bool(args, "overwrite")?;
+    // parquet("out", partition_by = year, month)
+    let partition_cols =
+        args::named_columns(args, "partition_by").map_err(|e| error::prefix(e, "parquet"))?;
 
     // If there is an input dataframe save it to disk.
     if let Some(df) = ctx.take_df() {
+        let path = match paths.as_slice() {
+            [path] => path,
+            _ => bail!(Error::io(
+                "parquet error: cannot write to a list of files".to_string()
+            )),
+        };
+
+        if !partition_cols.is_empty() {
+            args::check_columns(&partition_cols, ctx.columns())
+                .map_err(|e| error::prefix(e, "parquet"))?;
+
+            partition::write(
+                path,
+                df.clone(),
+                &partition_cols,
+                overwrite,
+                "parquet",
+                |df| ctx.collect(df, "parquet"),
+                |file, df| ParquetWriter::new(file).finish(df).map(|_| ()),
+            )
+            .map_err(|e| error::prefix(e, "parquet"))?;
+
+            ctx.set_df(df)?;
+
+            return Ok(());
+        }
+
         if !overwrite && path.exists() {
-            bail!("parquet error: file '{}' already exists.", path.display());
+            bail!(Error::io(format!(
+                "parquet error: file '{}' already exists.",
+                path.display()
+            )));
         }
 
-        let file = std::fs::File::create(&path)
-            .map_err(|e| anyhow!("parquet error: cannot create file '{}' {e}", path.display()))?;
+        let file = std::fs::File::create(path).map_err(|e| {
+            Error::io(format!(
+                "parquet error: cannot create file '{}' {e}",
+                path.display()
+            ))
+        })?;
 
-        let mut out_df = df.clone().collect()?;
+        let mut out_df = ctx.collect(df.clone(), "parquet")?;
         ctx.set_df(df)?;
 
         ParquetWriter::new(file).finish(&mut out_df)?;
     } else {
         // Read the data frame and set it as input for the next task.
-        let df = LazyFrame::scan_parquet(&path, ScanArgsParquet::default())
-            .map_err(|e| anyhow!("parquet error: cannot read file '{}' {e}", path.display()))?;
+        let df = files::read("parquet", &paths, |path| {
+            let df = LazyFrame::scan_parquet(path, ScanArgsParquet::default()).map_err(|e| {
+                Error::io(format!(
+                    "parquet error: cannot read file '{}' {e}",
+                    path.display()
+                ))
+            })?;
+            Ok(df)
+        })?;
         ctx.set_df(df)?;
     }
 
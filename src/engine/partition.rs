@@ -0,0 +1,136 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::error::Error;
+
+/// Writes a dataframe to a hive style partitioned directory.
+///
+/// Each group of `columns` values is written to its own file in a directory
+/// like `root/year=2022/month=11/part-0.parquet`, the partition columns are
+/// encoded in the path and are not included in the file. Partitions are
+/// collected one at a time with `collect`, with `overwrite` the partitions
+/// already in `root` are removed before writing.
+pub fn write<C, F>(
+    root: &Path,
+    mut df: LazyFrame,
+    columns: &[String],
+    overwrite: bool,
+    extension: &str,
+    mut collect: C,
+    mut writer: F,
+) -> Result<()>
+where
+    C: FnMut(LazyFrame) -> Result<DataFrame>,
+    F: FnMut(File, &mut DataFrame) -> PolarsResult<()>,
+{
+    if root.is_file() {
+        bail!(Error::io(format!("'{}' is a file", root.display())));
+    }
+
+    if columns.len() == df.schema()?.len() {
+        bail!("cannot partition by all columns");
+    }
+
+    if root.is_dir() && root.read_dir()?.next().is_some() {
+        if !overwrite {
+            bail!(Error::io(format!(
+                "directory '{}' is not empty",
+                root.display()
+            )));
+        }
+
+        clear_partitions(root)?;
+    }
+
+    let keys = columns.iter().map(|c| col(c)).collect::<Vec<_>>();
+    let values = collect(
+        df.clone()
+            .select(&keys)
+            .unique_stable(None, UniqueKeepStrategy::First),
+    )?;
+
+    for row in 0..values.height() {
+        let mut dir = root.to_path_buf();
+        let mut predicate = lit(true);
+
+        for column in columns {
+            let value = values.column(column)?.get(row)?;
+            dir.push(format!("{column}={}", path_value(&value)));
+
+            predicate = predicate.and(match value {
+                AnyValue::Null => col(column).is_null(),
+                value => col(column).eq(Expr::Literal(LiteralValue::try_from(value)?)),
+            });
+        }
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| Error::io(format!("cannot create directory '{}' {e}", dir.display())))?;
+
+        let path = dir.join(format!("part-0.{extension}"));
+        let file = File::create(&path)
+            .map_err(|e| Error::io(format!("cannot create file '{}' {e}", path.display())))?;
+
+        let mut out_df = collect(df.clone().filter(predicate).drop(columns))?;
+        writer(file, &mut out_df)?;
+    }
+
+    Ok(())
+}
+
+/// Removes the partitions in a directory, fails if the directory has entries
+/// that are not partitions so that other files are never removed.
+fn clear_partitions(root: &Path) -> Result<()> {
+    let mut partitions = Vec::new();
+
+    for entry in root.read_dir()? {
+        let path = entry?.path();
+        let is_partition = path.is_dir()
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.contains('='));
+
+        if !is_partition {
+            bail!(Error::io(format!(
+                "cannot overwrite directory '{}', '{}' is not a partition",
+                root.display(),
+                path.display()
+            )));
+        }
+
+        partitions.push(path);
+    }
+
+    for path in partitions {
+        fs::remove_dir_all(&path)
+            .map_err(|e| Error::io(format!("cannot remove directory '{}' {e}", path.display())))?;
+    }
+
+    Ok(())
+}
+
+/// Formats a partition value escaping characters that are not valid in a path.
+fn path_value(value: &AnyValue<'_>) -> String {
+    let value = match value {
+        AnyValue::Null => return "__HIVE_DEFAULT_PARTITION__".to_string(),
+        AnyValue::String(s) => s.to_string(),
+        AnyValue::StringOwned(s) => s.to_string(),
+        v => v.to_string(),
+    };
+
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '/' | '\\' | '=' | '%' | ':' | '"' | '*' | '?' | '<' | '>' | '|' => {
+                escaped.push_str(&format!("%{:02X}", c as u32));
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
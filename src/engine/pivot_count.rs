@@ -0,0 +1,63 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates a pivot_count call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(df) = ctx.take_df() {
+        let schema_cols = ctx.columns();
+
+        // pivot_count(VendorID, payment_type)
+        let index = args::identifier(&args[0]);
+        let values = args::identifier(&args[1]);
+        for column in [&index, &values] {
+            if !schema_cols.contains(column) {
+                let err = args::unknown_column(column, schema_cols);
+                return Err(error::prefix(err, "pivot_count"));
+            }
+        }
+
+        if index == values {
+            bail!("pivot_count error: index and values must be different columns");
+        }
+
+        // Each distinct non null value becomes a column with the number of
+        // rows that have that value for an index value.
+        let categories = df
+            .clone()
+            .select([col(&values).drop_nulls().unique().sort(Default::default())])
+            .select([col(&values).cast(DataType::String)])
+            .collect()
+            .map_err(|e| error::prefix(e, "pivot_count"))?;
+
+        let mut counts = Vec::with_capacity(categories.height());
+        for category in categories.column(&values)?.str()?.into_no_null_iter() {
+            if category == index {
+                bail!("pivot_count error: value '{category}' is also the index column name");
+            }
+
+            let matches = col(&values).cast(DataType::String).eq(lit(category));
+            counts.push(matches.sum().cast(DataType::UInt32).alias(category));
+        }
+
+        let df = df
+            .group_by([col(&index)])
+            .agg(counts)
+            .sort([&index], Default::default());
+
+        ctx.set_df(df)?;
+    } else if ctx.is_grouping() {
+        bail!("pivot_count error: must call summarize after a group_by");
+    } else {
+        bail!("pivot_count error: missing input dataframe");
+    }
+
+    Ok(())
+}
@@ -23,6 +23,18 @@ enum RelocateTo {
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(df) = ctx.take_df() {
         let schema_cols = ctx.columns();
+
+        // Report all unknown columns in one go.
+        let columns = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Expr::BinaryOp(_, Operator::Assign, rhs) => Some(args::identifier(rhs)),
+                Expr::Identifier(column) => Some(column.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        args::check_columns(&columns, schema_cols).map_err(|e| error::prefix(e, "relocate"))?;
+
         let mut relocate_cols = Vec::<&str>::new();
         let mut relocate_to = RelocateTo::Default;
 
@@ -33,24 +45,14 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
                     let dest = args::identifier(lhs);
                     let pos = args::identifier(rhs);
 
-                    if !schema_cols.contains(&pos) {
-                        bail!("relocate error: Unknown {dest} column {pos}");
-                    }
-
                     relocate_to = if dest == "before" {
                         RelocateTo::Before(pos)
                     } else {
                         RelocateTo::After(pos)
                     };
                 }
-                Expr::Identifier(column) => {
-                    if !schema_cols.contains(column) {
-                        bail!("relocate error: Unknown column {column}");
-                    }
-
-                    if !relocate_cols.contains(&column.as_str()) {
-                        relocate_cols.push(column);
-                    }
+                Expr::Identifier(column) if !relocate_cols.contains(&column.as_str()) => {
+                    relocate_cols.push(column);
                 }
                 _ => {}
             }
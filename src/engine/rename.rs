@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use anyhow::{bail, Result};
 use polars::prelude::*;
+use regex::Regex;
 
 use crate::parser::{Expr, Operator};
 
@@ -12,22 +13,60 @@ use super::*;
 /// Parameters are checked before evaluation by the typing module.
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(df) = ctx.take_df() {
-        let mut schema_cols = ctx.columns().iter().map(|c| col(c)).collect::<Vec<_>>();
+        // Report all unknown columns in one go.
+        let columns = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Expr::BinaryOp(_, Operator::Assign, rhs) => Some(args::identifier(rhs)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        args::check_columns(&columns, ctx.columns()).map_err(|e| error::prefix(e, "rename"))?;
+
+        // Pairs of original and new column names.
+        let mut names = ctx
+            .columns()
+            .iter()
+            .map(|c| (c.clone(), c.clone()))
+            .collect::<Vec<_>>();
 
         for arg in args {
-            if let Expr::BinaryOp(lhs, Operator::Assign, rhs) = arg {
-                let alias = args::identifier(lhs);
-                let column = args::identifier(rhs);
-
-                if let Some(idx) = schema_cols.iter().position(|c| c == &col(&column)) {
-                    schema_cols[idx] = schema_cols[idx].clone().alias(&alias);
-                } else {
-                    bail!("rename error: Unknown column {column}");
+            match arg {
+                Expr::BinaryOp(lhs, Operator::Assign, rhs) => {
+                    // rename(alias = column)
+                    let alias = args::identifier(lhs);
+                    let column = args::identifier(rhs);
+
+                    if let Some((_, name)) = names.iter_mut().find(|(c, _)| c == &column) {
+                        *name = alias;
+                    }
+                }
+                Expr::Function(name, args) if name == "replace" => {
+                    // rename(replace("regex", "replacement"))
+                    let pattern = args::string(&args[0]);
+                    let replacement = args::string(&args[1]);
+                    let re = Regex::new(&pattern)
+                        .map_err(|_| anyhow!("rename error: invalid replace regex '{pattern}'"))?;
+
+                    for (_, name) in names.iter_mut() {
+                        *name = re.replace_all(name, replacement.as_str()).into_owned();
+                    }
                 }
+                _ => {}
+            }
+        }
+
+        for (idx, (_, name)) in names.iter().enumerate() {
+            if names[..idx].iter().any(|(_, n)| n == name) {
+                bail!("rename error: duplicate column name '{name}'");
             }
         }
 
-        ctx.set_df(df.select(&schema_cols))?;
+        let columns = names
+            .iter()
+            .map(|(column, name)| col(column).alias(name))
+            .collect::<Vec<_>>();
+        ctx.set_df(df.select(&columns))?;
     } else if ctx.is_grouping() {
         bail!("rename error: must call summarize after a group_by");
     } else {
@@ -0,0 +1,112 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates a sample_n call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval_n(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    let n = args::number(&args[0]);
+    if n < 0.0 || n.fract() != 0.0 {
+        bail!("sample_n error: the number of rows must be a non negative integer");
+    }
+
+    sample(args, ctx, "sample_n", move |_| n as usize)
+}
+
+/// Evaluates a sample_frac call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval_frac(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    let frac = args::number(&args[0]);
+    if !(0.0..=1.0).contains(&frac) {
+        bail!("sample_frac error: the fraction must be between 0 and 1");
+    }
+
+    sample(args, ctx, "sample_frac", move |height| {
+        (height as f64 * frac) as usize
+    })
+}
+
+/// Samples the input dataframe rows, after a `group_by` each group is sampled
+/// separately and the result is not grouped.
+///
+/// Sampling runs when the dataframe is collected, with a seed each group gets
+/// its own seed derived from the seed plus the group index.
+fn sample<F>(args: &[Expr], ctx: &mut Context, fname: &'static str, size: F) -> Result<()>
+where
+    F: Fn(usize) -> usize + Send + Sync + 'static,
+{
+    let seed = args::named_usize(args, "seed")?.map(|seed| seed as u64);
+
+    if let Some(df) = ctx.take_df() {
+        let df = df.map(
+            move |df| {
+                let rows = (0..df.height() as IdxSize).collect();
+                let idx = sample_rows(rows, size(df.height()), seed)?;
+                df.take(&IdxCa::from_vec("", idx))
+            },
+            no_pushdown(),
+            None,
+            Some(fname),
+        );
+        ctx.set_df(df)?;
+    } else if let Some(group) = ctx.take_group() {
+        let keys = ctx.group_columns().to_vec();
+        let df = LazyFrame::from(group.logical_plan).map(
+            move |df| {
+                let groups = df.group_by_stable(&keys)?.take_groups();
+
+                let mut idx = Vec::with_capacity(df.height());
+                for (group_idx, group) in groups.iter().enumerate() {
+                    let rows = match group {
+                        GroupsIndicator::Idx((_, rows)) => rows.to_vec(),
+                        GroupsIndicator::Slice([first, len]) => (first..first + len).collect(),
+                    };
+
+                    let n = size(rows.len());
+                    let seed = seed.map(|seed| seed.wrapping_add(group_idx as u64));
+                    idx.extend(sample_rows(rows, n, seed)?);
+                }
+
+                df.take(&IdxCa::from_vec("", idx))
+            },
+            no_pushdown(),
+            None,
+            Some(fname),
+        );
+        ctx.set_df(df)?;
+    } else {
+        bail!("{fname} error: missing input dataframe");
+    }
+
+    Ok(())
+}
+
+/// Optimizations for the sampling step, later filters, projections, and slices
+/// must not be moved before the sample as they would change the sampled rows.
+fn no_pushdown() -> AllowedOptimizations {
+    AllowedOptimizations {
+        predicate_pushdown: false,
+        projection_pushdown: false,
+        slice_pushdown: false,
+        ..Default::default()
+    }
+}
+
+/// Samples `n` of the given row indices without replacement keeping their
+/// order, returns all the rows if there are fewer than `n`.
+fn sample_rows(rows: Vec<IdxSize>, n: usize, seed: Option<u64>) -> PolarsResult<Vec<IdxSize>> {
+    if n >= rows.len() {
+        return Ok(rows);
+    }
+
+    let idx = IdxCa::from_vec("", rows);
+    let idx = idx.sample_n(n, false, false, seed)?.sort(false);
+    Ok(idx.into_no_null_iter().collect())
+}
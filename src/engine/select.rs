@@ -1,7 +1,6 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
 use anyhow::{bail, Result};
-use polars::lazy::dsl::Expr as PolarsExpr;
 use polars::prelude::*;
 
 use crate::parser::{Expr, Operator};
@@ -14,45 +13,62 @@ use super::*;
 pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     if let Some(df) = ctx.take_df() {
         let schema_cols = ctx.columns();
-        let mut select_columns = Vec::new();
+
+        // Report all unknown columns in one go.
+        let columns = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Expr::BinaryOp(_, Operator::Assign, rhs) => Some(args::identifier(rhs)),
+                Expr::Identifier(column) => Some(column.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        args::check_columns(&columns, schema_cols).map_err(|e| error::prefix(e, "select"))?;
+
+        // Output columns as (name, column, explicit) to detect duplicate names,
+        // a column matched by a pattern and also selected by name is kept once.
+        let mut outputs: Vec<(String, String, bool)> = Vec::new();
+        let mut add_output = |name: String, column: String, explicit: bool| match outputs
+            .iter()
+            .find(|(n, _, _)| n == &name)
+        {
+            Some((_, c, e)) if c == &column && !(explicit && *e) => Ok(()),
+            Some(_) => Err(anyhow!("select error: duplicate column name '{name}'")),
+            None => {
+                outputs.push((name, column, explicit));
+                Ok(())
+            }
+        };
 
         for arg in args {
             match arg {
                 Expr::Function(_, _) => {
-                    let mut filter_cols = filter_columns(arg, schema_cols, false);
-                    filter_cols.retain(|e| !select_columns.contains(e));
-                    select_columns.extend(filter_cols);
+                    for column in filter_columns(arg, schema_cols, false) {
+                        add_output(column.clone(), column, false)?;
+                    }
                 }
                 Expr::UnaryOp(Operator::Not, expr) => {
-                    let mut filter_cols = filter_columns(expr, schema_cols, true);
-                    filter_cols.retain(|e| !select_columns.contains(e));
-                    select_columns.extend(filter_cols);
+                    for column in filter_columns(expr, schema_cols, true) {
+                        add_output(column.clone(), column, false)?;
+                    }
                 }
                 Expr::BinaryOp(lhs, Operator::Assign, rhs) => {
                     // select(alias = column)
-                    let alias = args::identifier(lhs);
-                    let column = args::identifier(rhs);
-                    let expr = col(&column).alias(&alias);
-
-                    if !select_columns.contains(&expr) {
-                        select_columns.push(expr);
-                    }
+                    add_output(args::identifier(lhs), args::identifier(rhs), true)?;
                 }
                 Expr::Identifier(column) => {
                     // select(column)
-                    if !schema_cols.contains(column) {
-                        bail!("select error: Unknown column {column}");
-                    }
-
-                    let expr = col(column);
-                    if !select_columns.contains(&expr) {
-                        select_columns.push(expr);
-                    }
+                    add_output(column.to_owned(), column.to_owned(), true)?;
                 }
                 _ => {}
             }
         }
 
+        let select_columns = outputs
+            .iter()
+            .map(|(name, column, _)| col(column).alias(name))
+            .collect::<Vec<_>>();
+
         ctx.set_df(df.select(&select_columns))?;
     } else if ctx.is_grouping() {
         bail!("select error: must call summarize after a group_by");
@@ -63,7 +79,40 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
-fn filter_columns(expr: &Expr, schema_cols: &[String], negate: bool) -> Vec<PolarsExpr> {
+/// Evaluates a reorder_like call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval_like(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(df) = ctx.take_df() {
+        // reorder_like(other_df)
+        let name = args::identifier(&args[0]);
+        let Some(mut other) = ctx.get_df(&name).cloned() else {
+            bail!("reorder_like error: undefined variable '{name}'");
+        };
+
+        let other_cols = other
+            .schema()
+            .map_err(|e| error::prefix(e, "reorder_like"))?
+            .iter_names()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        args::check_columns(&other_cols, ctx.columns())
+            .map_err(|e| error::prefix(e, "reorder_like"))?;
+
+        let columns = other_cols.iter().map(|c| col(c)).collect::<Vec<_>>();
+        ctx.set_df(df.select(&columns))?;
+    } else if ctx.is_grouping() {
+        bail!("reorder_like error: must call summarize after a group_by");
+    } else {
+        bail!("reorder_like error: missing input dataframe");
+    }
+
+    Ok(())
+}
+
+/// Returns the columns that match a `starts_with`, `ends_with`, or `contains`
+/// selector, or the ones that don't match when `negate` is true.
+pub fn filter_columns(expr: &Expr, schema_cols: &[String], negate: bool) -> Vec<String> {
     match expr {
         Expr::Function(name, args) if name == "starts_with" => {
             // select(starts_with("pattern"))
@@ -71,7 +120,7 @@ fn filter_columns(expr: &Expr, schema_cols: &[String], negate: bool) -> Vec<Pola
             schema_cols
                 .iter()
                 .filter(|c| c.starts_with(&pattern) ^ negate)
-                .map(|c| col(c))
+                .cloned()
                 .collect()
         }
         Expr::Function(name, args) if name == "ends_with" => {
@@ -80,7 +129,7 @@ fn filter_columns(expr: &Expr, schema_cols: &[String], negate: bool) -> Vec<Pola
             schema_cols
                 .iter()
                 .filter(|c| c.ends_with(&pattern) ^ negate)
-                .map(|c| col(c))
+                .cloned()
                 .collect()
         }
         Expr::Function(name, args) if name == "contains" => {
@@ -89,7 +138,7 @@ fn filter_columns(expr: &Expr, schema_cols: &[String], negate: bool) -> Vec<Pola
             schema_cols
                 .iter()
                 .filter(|c| c.contains(&pattern) ^ negate)
-                .map(|c| col(c))
+                .cloned()
                 .collect()
         }
         _ => Vec::new(),
@@ -9,9 +9,17 @@ use super::*;
 /// Evaluates a show call.
 ///
 /// Parameters are checked before evaluation by the typing module.
-pub fn eval(_args: &[Expr], ctx: &mut Context) -> Result<()> {
-    if let Some(df) = ctx.take_df() {
-        let df = df.collect()?;
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(Expr::Identifier(name)) = args.first() {
+        // Peek at a variable without consuming the active dataframe or group.
+        let Some(df) = ctx.get_df(name).cloned() else {
+            bail!("show error: undefined variable '{name}'");
+        };
+
+        let df = ctx.collect(df, "show")?;
+        ctx.print(df)?;
+    } else if let Some(df) = ctx.take_df() {
+        let df = ctx.collect(df, "show")?;
         ctx.print(df)?;
     } else if ctx.is_grouping() {
         bail!("show error: must call summarize after a group_by");
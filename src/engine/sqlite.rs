@@ -0,0 +1,143 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::parser::{Expr, Operator};
+
+use super::*;
+
+/// Evaluates a sqlite call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    let path = PathBuf::from(args::string(&args[0]));
+
+    if ctx.take_df().is_some() {
+        bail!("sqlite error: writing a dataframe to sqlite is not supported");
+    }
+
+    // sqlite("db.sqlite", "table") or sqlite("db.sqlite", query = "select ...")
+    let query = match &args[1] {
+        Expr::BinaryOp(_, Operator::Assign, rhs) => args::string(rhs),
+        expr => {
+            let table = args::string(expr);
+            format!("SELECT * FROM \"{}\"", table.replace('"', "\"\""))
+        }
+    };
+
+    let df = read(&path, &query)?;
+    ctx.set_df(df.lazy())?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn read(_path: &Path, _query: &str) -> Result<DataFrame> {
+    bail!("sqlite error: dply was built without the sqlite feature");
+}
+
+/// Reads the result of a query on a read only database into a dataframe.
+#[cfg(feature = "sqlite")]
+fn read(path: &Path, query: &str) -> Result<DataFrame> {
+    use rusqlite::{Connection, OpenFlags};
+
+    let conn =
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|e| {
+            Error::io(format!(
+                "sqlite error: cannot open '{}' {e}",
+                path.display()
+            ))
+        })?;
+
+    read_query(&conn, query).map_err(|e| error::prefix(e, "sqlite"))
+}
+
+/// Reads the result of a query into a dataframe.
+#[cfg(feature = "sqlite")]
+fn read_query(conn: &rusqlite::Connection, query: &str) -> Result<DataFrame> {
+    use rusqlite::types::Value;
+
+    let mut stmt = conn.prepare(query)?;
+
+    let names = stmt
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let mut values = vec![Vec::new(); names.len()];
+
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        for (idx, column) in values.iter_mut().enumerate() {
+            column.push(row.get::<_, Value>(idx)?);
+        }
+    }
+
+    let columns = names
+        .iter()
+        .zip(values)
+        .map(|(name, values)| to_series(name, &values))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Converts sqlite values to a series.
+///
+/// Sqlite columns can store values of any type, the series type is inferred
+/// from the column values: integers, floats if there are any reals, and
+/// strings if there are any text values.
+#[cfg(feature = "sqlite")]
+fn to_series(name: &str, values: &[rusqlite::types::Value]) -> Result<Series> {
+    use rusqlite::types::Value;
+
+    let has = |f: fn(&Value) -> bool| values.iter().any(f);
+
+    if has(|v| matches!(v, Value::Blob(_))) {
+        if has(|v| !matches!(v, Value::Blob(_) | Value::Null)) {
+            bail!("column '{name}' has mixed blob and non blob values");
+        }
+
+        let blobs = values
+            .iter()
+            .map(|v| match v {
+                Value::Blob(b) => Some(b.as_slice()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Series::new(name, blobs))
+    } else if has(|v| matches!(v, Value::Text(_))) {
+        let strings = values
+            .iter()
+            .map(|v| match v {
+                Value::Integer(i) => Some(i.to_string()),
+                Value::Real(f) => Some(f.to_string()),
+                Value::Text(s) => Some(s.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Series::new(name, strings))
+    } else if has(|v| matches!(v, Value::Real(_))) {
+        let floats = values
+            .iter()
+            .map(|v| match v {
+                Value::Integer(i) => Some(*i as f64),
+                Value::Real(f) => Some(*f),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Series::new(name, floats))
+    } else {
+        let ints = values
+            .iter()
+            .map(|v| match v {
+                Value::Integer(i) => Some(*i),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        Ok(Series::new(name, ints))
+    }
+}
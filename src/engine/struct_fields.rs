@@ -0,0 +1,51 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates a struct_fields call.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    if let Some(mut df) = ctx.take_df() {
+        let column = args::identifier(&args[0]);
+        let schema = df.schema().map_err(|e| error::prefix(e, "struct_fields"))?;
+
+        let Some(dtype) = schema.get(&column) else {
+            let err = args::unknown_column(&column, schema.iter_names());
+            return Err(error::prefix(err, "struct_fields"));
+        };
+
+        // Show the fields of the structs inside lists like list[struct[4]].
+        let mut inner = dtype;
+        while let DataType::List(dtype) | DataType::Array(dtype, _) = inner {
+            inner = dtype;
+        }
+
+        let DataType::Struct(fields) = inner else {
+            bail!("struct_fields error: column '{column}' must be a struct, found {dtype}");
+        };
+
+        let names = fields.iter().map(|f| f.name().as_str()).collect::<Vec<_>>();
+        let types = fields
+            .iter()
+            .map(|f| f.data_type().to_string())
+            .collect::<Vec<_>>();
+
+        let fields_df = DataFrame::new(vec![
+            Series::new("field", names),
+            Series::new("type", types),
+        ])?;
+        ctx.print(fields_df)?;
+    } else if ctx.is_grouping() {
+        bail!("struct_fields error: must call summarize after a group_by");
+    } else {
+        bail!("struct_fields error: missing input dataframe");
+    }
+
+    Ok(())
+}
@@ -30,15 +30,15 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
             .logical_plan
             .compute_schema()
             .map_err(anyhow::Error::from)
-            .and_then(|schema| eval_args(args, ctx, &schema, true))
-            .map_err(|e| anyhow!("summarize error: {e}"))?;
+            .and_then(|schema| eval_args(args, &schema, true))
+            .map_err(|e| error::prefix(e, "summarize"))?;
         ctx.set_df(group.agg(&columns))?;
     } else if let Some(mut df) = ctx.take_df() {
         let columns = df
             .schema()
             .map_err(anyhow::Error::from)
-            .and_then(|schema| eval_args(args, ctx, &schema, false))
-            .map_err(|e| anyhow!("summarize error: {e}"))?;
+            .and_then(|schema| eval_args(args, &schema, false))
+            .map_err(|e| error::prefix(e, "summarize"))?;
         ctx.set_df(df.select(&columns))?;
     } else {
         bail!("summarize error: missing input group or dataframe");
@@ -47,13 +47,7 @@ pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
-fn eval_args(
-    args: &[Expr],
-    ctx: &mut Context,
-    schema: &Schema,
-    grouping: bool,
-) -> Result<Vec<PolarsExpr>> {
-    let schema_cols = ctx.columns();
+fn eval_args(args: &[Expr], schema: &Schema, grouping: bool) -> Result<Vec<PolarsExpr>> {
     let mut aliases = HashSet::new();
     let mut columns = Vec::new();
 
@@ -67,39 +61,7 @@ fn eval_args(
 
                 aliases.insert(alias.clone());
 
-                let column = match rhs.as_ref() {
-                    Expr::Function(name, _) if name == "n" => Ok(col(&schema_cols[0]).count()),
-                    Expr::Function(name, args) if name == "list" => args::column(&args[0], schema)
-                        .map(|c| if grouping { c } else { c.implode() }),
-                    Expr::Function(name, args) if name == "max" => {
-                        args::column(&args[0], schema).map(|c| c.max())
-                    }
-                    Expr::Function(name, args) if name == "mean" => {
-                        args::column(&args[0], schema).map(|c| c.mean())
-                    }
-                    Expr::Function(name, args) if name == "median" => {
-                        args::column(&args[0], schema).map(|c| c.median())
-                    }
-                    Expr::Function(name, args) if name == "min" => {
-                        args::column(&args[0], schema).map(|c| c.min())
-                    }
-                    Expr::Function(name, args) if name == "quantile" => {
-                        let quantile = args::number(&args[1]);
-                        args::column(&args[0], schema)
-                            .map(|c| c.quantile(lit(quantile), QuantileInterpolOptions::Linear))
-                    }
-                    Expr::Function(name, args) if name == "sd" => {
-                        args::column(&args[0], schema).map(|c| c.std(1))
-                    }
-                    Expr::Function(name, args) if name == "sum" => {
-                        args::column(&args[0], schema).map(|c| c.sum())
-                    }
-                    Expr::Function(name, args) if name == "var" => {
-                        args::column(&args[0], schema).map(|c| c.var(1))
-                    }
-                    _ => panic!("Unexpected summarize expression {rhs}"),
-                }?;
-
+                let column = eval_aggregate(rhs, schema, grouping)?;
                 columns.push(column.alias(&alias));
             }
             _ => panic!("Unexpected summarize expression: {arg}"),
@@ -108,3 +70,89 @@ fn eval_args(
 
     Ok(columns)
 }
+
+/// Evaluates an aggregate or an arithmetic expression of aggregates.
+pub fn eval_aggregate(expr: &Expr, schema: &Schema, grouping: bool) -> Result<PolarsExpr> {
+    match expr {
+        Expr::Function(name, _) if name == "n" => {
+            // A bare `len()` on a scan loses its alias in the fast count path.
+            Ok(schema
+                .iter_names()
+                .next()
+                .map_or_else(len, |c| col(c).len()))
+        }
+        Expr::Function(name, args) if name == "all" => {
+            filter::eval_expr(&args[0], schema).map(|c| c.all(true))
+        }
+        Expr::Function(name, args) if name == "any" => {
+            filter::eval_expr(&args[0], schema).map(|c| c.any(true))
+        }
+        Expr::Function(name, args) if name == "keep" => {
+            let column = args::identifier(&args[0]);
+            args::column(&args[0], schema).map(|c| keep(c, column))
+        }
+        Expr::Function(name, args) if name == "list" => {
+            args::column(&args[0], schema).map(|c| if grouping { c } else { c.implode() })
+        }
+        Expr::Function(name, args) if name == "max" => {
+            args::column(&args[0], schema).map(|c| c.max())
+        }
+        Expr::Function(name, args) if name == "mean" => {
+            args::column(&args[0], schema).map(|c| c.mean())
+        }
+        Expr::Function(name, args) if name == "median" => {
+            args::column(&args[0], schema).map(|c| c.median())
+        }
+        Expr::Function(name, args) if name == "min" => {
+            args::column(&args[0], schema).map(|c| c.min())
+        }
+        Expr::Function(name, args) if name == "quantile" => {
+            let quantile = args::number(&args[1]);
+            args::column(&args[0], schema)
+                .map(|c| c.quantile(lit(quantile), QuantileInterpolOptions::Linear))
+        }
+        Expr::Function(name, args) if name == "sd" => {
+            args::column(&args[0], schema).map(|c| c.std(1))
+        }
+        Expr::Function(name, args) if name == "sum" => {
+            args::column(&args[0], schema).map(|c| c.sum())
+        }
+        Expr::Function(name, args) if name == "var" => {
+            args::column(&args[0], schema).map(|c| c.var(1))
+        }
+        Expr::BinaryOp(lhs, op, rhs) => {
+            // Arithmetic on aggregates, like sum(tips) / sum(amount).
+            let mut lhs_agg = eval_aggregate(lhs, schema, grouping)?;
+            let mut rhs_agg = eval_aggregate(rhs, schema, grouping)?;
+
+            // Integer aggregates like sum(n) / n() use float division.
+            if matches!(op, Operator::Divide) {
+                lhs_agg = lhs_agg.cast(DataType::Float64);
+                rhs_agg = rhs_agg.cast(DataType::Float64);
+            }
+
+            mutate::arith(lhs_agg, op, rhs_agg, (lhs, rhs), schema)
+        }
+        Expr::Number(n) => Ok(lit(*n)),
+        _ => panic!("Unexpected summarize expression {expr}"),
+    }
+}
+
+/// Returns the first value of a column checking that it has a single distinct
+/// value in each group.
+fn keep(column: PolarsExpr, name: String) -> PolarsExpr {
+    map_multiple(
+        move |series: &mut [Series]| {
+            let counts = series[0].cast(&DataType::UInt32)?;
+            if counts.u32()?.into_iter().any(|n| n.unwrap_or(0) > 1) {
+                polars_bail!(
+                    ComputeError: "`keep` column '{name}' has more than one value in a group"
+                );
+            }
+
+            Ok(Some(std::mem::take(&mut series[1])))
+        },
+        [column.clone().n_unique(), column.first()],
+        GetOutput::map_fields(|fields| Ok(fields[1].clone())),
+    )
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::{bail, Result};
+use polars::prelude::*;
+
+use crate::parser::Expr;
+
+use super::*;
+
+/// Evaluates a top_n call.
+///
+/// After a `group_by` returns the rows with the `n` highest values in each
+/// group, otherwise the `n` rows with the highest values.
+///
+/// Parameters are checked before evaluation by the typing module.
+pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+    let n = args::number(&args[0]);
+    if n < 1.0 || n.fract() != 0.0 {
+        bail!("top_n error: the number of rows must be a positive integer");
+    }
+
+    let sort_opts = SortMultipleOptions {
+        descending: vec![true],
+        nulls_last: vec![true],
+        maintain_order: true,
+        ..Default::default()
+    };
+
+    if let Some(mut df) = ctx.take_df() {
+        let schema = df.schema().map_err(|e| error::prefix(e, "top_n"))?;
+        let column = args::column(&args[1], &schema).map_err(|e| error::prefix(e, "top_n"))?;
+
+        ctx.set_df(df.sort_by_exprs([column], sort_opts).limit(n as IdxSize))?;
+    } else if let Some(group) = ctx.take_group() {
+        let mut df = LazyFrame::from(group.logical_plan);
+        let schema = df.schema().map_err(|e| error::prefix(e, "top_n"))?;
+        let column = args::column(&args[1], &schema).map_err(|e| error::prefix(e, "top_n"))?;
+
+        // Groups are returned in the order of their highest value and the
+        // grouping columns keep their original position.
+        let keys = ctx
+            .group_columns()
+            .iter()
+            .map(|c| col(c))
+            .collect::<Vec<_>>();
+        let columns = schema.iter_names().map(|c| col(c)).collect::<Vec<_>>();
+
+        let df = df
+            .sort_by_exprs([column], sort_opts)
+            .group_by_stable(keys)
+            .head(Some(n as usize))
+            .select(columns);
+        ctx.set_df(df)?;
+    } else {
+        bail!("top_n error: missing input dataframe");
+    }
+
+    Ok(())
+}
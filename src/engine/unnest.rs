@@ -19,30 +19,70 @@ use crate::parser::Expr;
 
 use super::*;
 
+/// Column types expanded by an unnest call.
+#[derive(Clone, Copy, PartialEq)]
+pub enum UnnestType {
+    /// Lists and structs, used by `unnest`.
+    Any,
+    /// Only lists, used by `unnest_list`.
+    List,
+    /// Only structs, used by `unnest_struct`.
+    Struct,
+}
+
+impl UnnestType {
+    fn name(self) -> &'static str {
+        match self {
+            UnnestType::Any => "unnest",
+            UnnestType::List => "unnest_list",
+            UnnestType::Struct => "unnest_struct",
+        }
+    }
+}
+
 /// Evaluates an unnest call.
 ///
 /// Parameters are checked before evaluation by the typing module.
-pub fn eval(args: &[Expr], ctx: &mut Context) -> Result<()> {
+pub fn eval(args: &[Expr], ctx: &mut Context, unnest_type: UnnestType) -> Result<()> {
+    let fname = unnest_type.name();
+
     if let Some(mut df) = ctx.take_df() {
         for arg in args {
             let column = args::identifier(arg);
-            let schema = df.schema().map_err(|e| anyhow!("unnest error: {e}"))?;
+            let schema = df.schema().map_err(|e| error::prefix(e, fname))?;
 
-            match schema.get(&column) {
-                Some(DataType::List(_)) => {
+            match (schema.get(&column), unnest_type) {
+                (Some(DataType::List(_)), UnnestType::Any | UnnestType::List) => {
                     df = df.explode(vec![col(&column)]);
                 }
-                Some(DataType::Struct(_)) => {
+                (Some(DataType::Struct(_)), UnnestType::Any | UnnestType::Struct) => {
                     df = df.unnest([&column]);
                 }
-                Some(_) => bail!("unnest error: '{column}' is not a list or struct type"),
-                None => bail!("unnest error: unknown column '{column}'"),
+                (Some(DataType::List(_)), UnnestType::Struct) => {
+                    bail!("{fname} error: '{column}' is a list, use unnest_list")
+                }
+                (Some(DataType::Struct(_)), UnnestType::List) => {
+                    bail!("{fname} error: '{column}' is a struct, use unnest_struct")
+                }
+                (Some(_), UnnestType::Any) => {
+                    bail!("{fname} error: '{column}' is not a list or struct type")
+                }
+                (Some(_), UnnestType::List) => {
+                    bail!("{fname} error: '{column}' is not a list type")
+                }
+                (Some(_), UnnestType::Struct) => {
+                    bail!("{fname} error: '{column}' is not a struct type")
+                }
+                (None, _) => {
+                    let err = args::unknown_column(&column, schema.iter_names());
+                    return Err(error::prefix(err, fname));
+                }
             }
         }
 
         ctx.set_df(df)?;
     } else {
-        bail!("unnest error: missing input dataframe");
+        bail!("{fname} error: missing input dataframe");
     }
 
     Ok(())
@@ -0,0 +1,291 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+
+//! Errors returned by the interpreter.
+use polars::prelude::PolarsError;
+
+use crate::fuzzy;
+
+/// An interpreter error.
+///
+/// The error kind can be used to distinguish errors in the input script, like
+/// an unknown column, from system errors, like a missing file.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The input script has a syntax error.
+    #[error("{message}")]
+    ParseError {
+        /// The error message.
+        message: String,
+        /// The error position in the script.
+        position: Option<Position>,
+    },
+    /// A function has been called with invalid arguments.
+    #[error("{message}")]
+    TypeError {
+        /// The error message.
+        message: String,
+        /// The position of the function in the script.
+        position: Option<Position>,
+    },
+    /// A column is not in the input dataframe.
+    #[error("{message}")]
+    UnknownColumn {
+        /// The unknown column name, the first one if there are many.
+        name: String,
+        /// Dataframe columns with a similar name.
+        suggestions: Vec<String>,
+        /// The error message.
+        message: String,
+        /// The position of the failed pipeline step in the script.
+        position: Option<Position>,
+    },
+    /// Reading or writing a file failed.
+    #[error("{message}")]
+    IoError {
+        /// The error message.
+        message: String,
+        /// The position of the failed pipeline step in the script.
+        position: Option<Position>,
+    },
+    /// The pipeline evaluation failed.
+    #[error("{message}")]
+    ExecutionError {
+        /// The error message.
+        message: String,
+        /// The position of the failed pipeline step in the script.
+        position: Option<Position>,
+    },
+    /// Checking a script found errors in more than one pipeline.
+    #[error("{}", fmt_errors(.0))]
+    CheckErrors(Vec<Error>),
+}
+
+/// A position in the input script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column number.
+    pub column: usize,
+}
+
+impl Error {
+    /// Returns the position in the script of the error.
+    ///
+    /// Parse errors have the position of the syntax error, other errors have
+    /// the position of the failed pipeline step.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::ParseError { position, .. }
+            | Self::TypeError { position, .. }
+            | Self::UnknownColumn { position, .. }
+            | Self::IoError { position, .. }
+            | Self::ExecutionError { position, .. } => *position,
+            Self::CheckErrors(_) => None,
+        }
+    }
+
+    /// Creates an IO error without a position.
+    pub(crate) fn io(message: String) -> Self {
+        Self::IoError {
+            message,
+            position: None,
+        }
+    }
+
+    /// Creates an unknown column error with suggestions from the given columns.
+    pub(crate) fn unknown_column<I, S>(name: &str, columns: I, message: String) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.as_ref().to_string())
+            .collect::<Vec<_>>();
+        let suggestions = fuzzy::Matcher::with_trigrams(name)
+            .closest(columns.iter().map(String::as_str))
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        Self::UnknownColumn {
+            name: name.to_string(),
+            suggestions,
+            message,
+            position: None,
+        }
+    }
+
+    /// Adds the failed pipeline step and its position to the error.
+    ///
+    /// Steps are numbered from 1 in each pipeline.
+    pub(crate) fn in_step(self, step: usize, name: &str, position: Position) -> Self {
+        let message = format!(
+            "in step {step} (`{name}`) at line {}: {self}",
+            position.line
+        );
+        self.with_message(message).with_position(position)
+    }
+
+    /// Sets the position of the error in the script.
+    fn with_position(self, position: Position) -> Self {
+        let position = Some(position);
+        match self {
+            Self::ParseError { message, .. } => Self::ParseError { message, position },
+            Self::TypeError { message, .. } => Self::TypeError { message, position },
+            Self::UnknownColumn {
+                name,
+                suggestions,
+                message,
+                ..
+            } => Self::UnknownColumn {
+                name,
+                suggestions,
+                message,
+                position,
+            },
+            Self::IoError { message, .. } => Self::IoError { message, position },
+            Self::ExecutionError { message, .. } => Self::ExecutionError { message, position },
+            Self::CheckErrors(errors) => Self::CheckErrors(errors),
+        }
+    }
+
+    /// Prefixes the error message with the function name, keeping the error kind.
+    ///
+    /// Messages that already have the prefix are not changed.
+    pub(crate) fn with_prefix(self, fname: &str) -> Self {
+        let prefix = |msg: String| {
+            if msg.starts_with(&format!("{fname} error: ")) {
+                msg
+            } else {
+                format!("{fname} error: {msg}")
+            }
+        };
+
+        match self {
+            Self::ParseError { message, position } => Self::ParseError {
+                message: prefix(message),
+                position,
+            },
+            Self::TypeError { message, position } => Self::TypeError {
+                message: prefix(message),
+                position,
+            },
+            Self::UnknownColumn {
+                name,
+                suggestions,
+                message,
+                position,
+            } => Self::UnknownColumn {
+                name,
+                suggestions,
+                message: prefix(message),
+                position,
+            },
+            Self::IoError { message, position } => Self::IoError {
+                message: prefix(message),
+                position,
+            },
+            Self::ExecutionError { message, position } => Self::ExecutionError {
+                message: prefix(message),
+                position,
+            },
+            Self::CheckErrors(errors) => {
+                Self::CheckErrors(errors.into_iter().map(|e| e.with_prefix(fname)).collect())
+            }
+        }
+    }
+
+    /// Returns a copy of this error with a new message, keeping the error kind.
+    pub(crate) fn with_message(&self, message: String) -> Self {
+        match self {
+            Self::ParseError { position, .. } => Self::ParseError {
+                message,
+                position: *position,
+            },
+            Self::TypeError { position, .. } => Self::TypeError {
+                message,
+                position: *position,
+            },
+            Self::UnknownColumn {
+                name,
+                suggestions,
+                position,
+                ..
+            } => Self::UnknownColumn {
+                name: name.clone(),
+                suggestions: suggestions.clone(),
+                message,
+                position: *position,
+            },
+            Self::IoError { position, .. } => Self::IoError {
+                message,
+                position: *position,
+            },
+            Self::ExecutionError { position, .. } => Self::ExecutionError {
+                message,
+                position: *position,
+            },
+            Self::CheckErrors(_) => Self::ExecutionError {
+                message,
+                position: None,
+            },
+        }
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        // Keep the context added to an interpreter error, like the pipeline
+        // step that failed, in the message.
+        if let Some(inner) = err.downcast_ref::<Error>() {
+            let mut message = Vec::new();
+            for e in err.chain() {
+                message.push(e.to_string());
+                if e.is::<Error>() {
+                    break;
+                }
+            }
+
+            return inner.with_message(message.join(": "));
+        }
+
+        let is_io = err
+            .chain()
+            .any(|e| e.is::<std::io::Error>() || e.downcast_ref().is_some_and(is_polars_io));
+
+        if is_io {
+            Self::io(err.to_string())
+        } else {
+            Self::ExecutionError {
+                message: err.to_string(),
+                position: None,
+            }
+        }
+    }
+}
+
+/// Formats a list of errors one per line.
+fn fmt_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prefixes an error message with the function name, keeping the error kind.
+pub(crate) fn prefix(err: impl Into<anyhow::Error>, fname: &str) -> anyhow::Error {
+    Error::from(err.into()).with_prefix(fname).into()
+}
+
+/// Returns true if a Polars error is caused by an IO error.
+fn is_polars_io(err: &PolarsError) -> bool {
+    match err {
+        PolarsError::IO { .. } => true,
+        PolarsError::Context { error, .. } => is_polars_io(error),
+        _ => false,
+    }
+}
@@ -1,21 +1,47 @@
 // Copyright (C) 2023 Vince Vasta
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashSet;
+
+/// Minimum fraction of the pattern trigrams that must be found in the text.
+const TRIGRAM_THRESHOLD: f64 = 0.5;
+
+/// Minimum trigram similarity for a name to be suggested.
+const MIN_SIMILARITY: f64 = 0.25;
+
+/// The score of a text that matches the pattern only by its trigrams.
+pub const TYPO_SCORE: u8 = 3;
+
 /// Simple fuzzy matcher.
 ///
 /// Inspired by: https://github.com/forrestthewoods/lib_fts
 pub struct Matcher {
     pattern: String,
+    trigrams: Option<HashSet<String>>,
 }
 
 impl Matcher {
     pub fn new(pattern: &str) -> Self {
         Self {
             pattern: pattern.to_lowercase(),
+            trigrams: None,
         }
     }
 
+    /// Creates a matcher that also matches text with typos using the pattern
+    /// trigrams, patterns shorter than 3 characters only match subsequences.
+    pub fn with_trigrams(pattern: &str) -> Self {
+        let pattern = pattern.to_lowercase();
+        let trigrams = (pattern.chars().count() >= 3).then(|| trigrams(&pattern));
+
+        Self { pattern, trigrams }
+    }
+
     pub fn is_match(&self, text: &str) -> bool {
+        self.is_subsequence(text) || self.is_trigram_match(text)
+    }
+
+    fn is_subsequence(&self, text: &str) -> bool {
         let mut pit = self.pattern.chars().peekable();
 
         for c in text.chars() {
@@ -30,6 +56,91 @@ impl Matcher {
 
         pit.peek().is_none()
     }
+
+    fn is_trigram_match(&self, text: &str) -> bool {
+        self.trigrams.as_ref().is_some_and(|pattern| {
+            let text = trigrams(&text.to_lowercase());
+            let common = pattern.intersection(&text).count();
+            common as f64 / pattern.len() as f64 >= TRIGRAM_THRESHOLD
+        })
+    }
+
+    /// Returns the trigram similarity between the pattern and the text, from 0
+    /// for no common trigrams to 1 for the same trigrams.
+    ///
+    /// Always returns 0 for matchers created without trigrams.
+    pub fn similarity(&self, text: &str) -> f64 {
+        self.trigrams.as_ref().map_or(0.0, |pattern| {
+            let text = trigrams(&text.to_lowercase());
+            let common = pattern.intersection(&text).count();
+            common as f64 / (pattern.len() + text.len() - common) as f64
+        })
+    }
+
+    /// Returns the names that are similar to the pattern, the most similar
+    /// first, used to suggest names for typos.
+    pub fn closest<'a, I>(&self, names: I) -> Vec<&'a str>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut similar = names
+            .into_iter()
+            .map(|name| (self.similarity(name), name))
+            .filter(|(similarity, _)| *similarity >= MIN_SIMILARITY)
+            .collect::<Vec<_>>();
+
+        // Ties are broken by name to make suggestions deterministic.
+        similar.sort_by(|(s1, n1), (s2, n2)| s2.total_cmp(s1).then(n1.cmp(n2)));
+        similar.into_iter().map(|(_, name)| name).collect()
+    }
+
+    /// Returns the match score for the text, lower is better.
+    ///
+    /// Prefix matches score 0, word boundary matches (after a `_`) score 1,
+    /// other subsequence matches score 2, and trigram matches score 3. Returns
+    /// `None` if there is no match.
+    pub fn score(&self, text: &str) -> Option<u8> {
+        if !self.is_subsequence(text) {
+            return self.is_trigram_match(text).then_some(TYPO_SCORE);
+        }
+
+        let text = text.to_lowercase();
+        if text.starts_with(&self.pattern) {
+            Some(0)
+        } else if text.split('_').any(|w| w.starts_with(&self.pattern)) {
+            Some(1)
+        } else {
+            Some(2)
+        }
+    }
+
+    /// Retains only matching entries sorting them by score and name.
+    pub fn rank(&self, entries: &mut Vec<String>) {
+        let mut scored = entries
+            .drain(..)
+            .filter_map(|s| self.score(&s).map(|score| (score, s.to_lowercase(), s)))
+            .collect::<Vec<_>>();
+
+        scored.sort();
+        entries.extend(scored.into_iter().map(|(_, _, s)| s));
+    }
+}
+
+/// Returns the trigrams of the words in the text, each word is padded with two
+/// spaces at the start and one at the end.
+fn trigrams(text: &str) -> HashSet<String> {
+    let mut trigrams = HashSet::new();
+
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+
+        let chars = format!("  {word} ").chars().collect::<Vec<_>>();
+        trigrams.extend(chars.windows(3).map(|w| w.iter().collect::<String>()));
+    }
+
+    trigrams
 }
 
 #[cfg(test)]
@@ -52,4 +163,130 @@ mod tests {
         assert!(!Matcher::new("eee").is_match("select"));
         assert!(!Matcher::new("stt").is_match("select"));
     }
+
+    #[test]
+    fn rank_matches() {
+        let columns = [
+            "VendorID",
+            "tpep_pickup_datetime",
+            "tpep_dropoff_datetime",
+            "passenger_count",
+            "trip_distance",
+            "payment_type",
+            "fare_amount",
+            "tip_amount",
+            "tolls_amount",
+            "total_amount",
+        ];
+
+        let rank = |pattern: &str| {
+            let mut entries = columns.iter().map(|s| s.to_string()).collect();
+            Matcher::new(pattern).rank(&mut entries);
+            entries
+        };
+
+        // Prefix matches first then subsequences.
+        assert_eq!(
+            rank("to"),
+            [
+                "tolls_amount",
+                "total_amount",
+                "tip_amount",
+                "tpep_dropoff_datetime"
+            ]
+        );
+
+        // Word boundary matches before subsequences.
+        assert_eq!(
+            rank("am"),
+            [
+                "fare_amount",
+                "tip_amount",
+                "tolls_amount",
+                "total_amount",
+                "payment_type",
+                "tpep_dropoff_datetime",
+                "tpep_pickup_datetime"
+            ]
+        );
+
+        // Ties are sorted alphabetically ignoring case.
+        assert_eq!(
+            rank("di"),
+            [
+                "trip_distance",
+                "tpep_dropoff_datetime",
+                "tpep_pickup_datetime",
+                "VendorID"
+            ]
+        );
+
+        assert_eq!(rank("ven"), ["VendorID"]);
+        assert!(rank("xyz").is_empty());
+    }
+
+    #[test]
+    fn trigram_match() {
+        assert!(Matcher::with_trigrams("totsl").is_match("total_amount"));
+        assert!(Matcher::with_trigrams("pasenger_cuont").is_match("passenger_count"));
+        assert!(Matcher::with_trigrams("Payment_Tipe").is_match("payment_type"));
+        assert!(!Matcher::with_trigrams("totsl").is_match("trip_distance"));
+        assert!(!Matcher::with_trigrams("xyz").is_match("total_amount"));
+
+        // Typos are not matched without trigrams.
+        assert!(!Matcher::new("totsl").is_match("total_amount"));
+
+        // Short patterns only match subsequences.
+        assert!(!Matcher::with_trigrams("ab").is_match("ba"));
+    }
+
+    #[test]
+    fn rank_trigram_matches() {
+        let mut entries = ["fare_amount", "total_amount", "tolls_amount", "tip_amount"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // Subsequence matches come before trigram matches.
+        Matcher::with_trigrams("tota").rank(&mut entries);
+        assert_eq!(entries, ["total_amount"]);
+
+        let mut entries = ["fare_amount", "total_amount", "tolls_amount", "tip_amount"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        Matcher::with_trigrams("toal_amount").rank(&mut entries);
+        assert_eq!(
+            entries,
+            ["total_amount", "fare_amount", "tip_amount", "tolls_amount"]
+        );
+    }
+
+    #[test]
+    fn trigram_similarity() {
+        let matcher = Matcher::with_trigrams("select");
+        assert_eq!(matcher.similarity("SELECT"), 1.0);
+        assert_eq!(matcher.similarity("xyz"), 0.0);
+        assert!(matcher.similarity("selct") > matcher.similarity("relocate"));
+
+        assert_eq!(Matcher::new("select").similarity("select"), 0.0);
+    }
+
+    #[test]
+    fn closest_names() {
+        let names = [
+            "fare_amount",
+            "total_amount",
+            "tolls_amount",
+            "trip_distance",
+        ];
+        let matcher = Matcher::with_trigrams("totl_amount");
+        assert_eq!(
+            matcher.closest(names),
+            ["total_amount", "tolls_amount", "fare_amount"]
+        );
+
+        assert!(Matcher::with_trigrams("riders").closest(names).is_empty());
+    }
 }
@@ -2,24 +2,85 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Interpreter for dply expressions.
-use anyhow::Result;
-
+pub use crate::config::OutputFormat;
+use crate::error::Error;
+use crate::parser::Expr;
 use crate::{engine, parser, typing};
+use std::path::PathBuf;
+use std::slice;
 
 /// Evaluates a dply script.
-pub fn eval(input: &str) -> Result<()> {
-    let pipelines = parser::parse(input)?;
-    typing::validate(&pipelines)?;
+pub fn eval(input: &str) -> Result<(), Error> {
+    eval_with_format(input, OutputFormat::Table)
+}
+
+/// Evaluates a dply script showing dataframes in the given format.
+pub fn eval_with_format(input: &str, format: OutputFormat) -> Result<(), Error> {
+    let pipelines = parse(input)?;
 
     let mut ctx = engine::Context::default();
+    ctx.set_output_format(format);
     engine::eval(&mut ctx, &pipelines)?;
 
     Ok(())
 }
 
 /// Evaluates a dply script with a string output.
-pub fn eval_to_string(input: &str) -> Result<String> {
+pub fn eval_to_string(input: &str) -> Result<String, Error> {
+    eval_to_string_with_format(input, OutputFormat::Table)
+}
+
+/// Evaluates a dply script with a string output in the given format.
+pub fn eval_to_string_with_format(input: &str, format: OutputFormat) -> Result<String, Error> {
+    let pipelines = parse(input)?;
+    Ok(engine::eval_to_string(&pipelines, format)?)
+}
+
+/// Checks a dply script without evaluating it.
+///
+/// The script is parsed and its functions arguments are validated. If sample
+/// `schemas` are given as `(reader_path, sample_path)` pairs the pipelines are
+/// also evaluated without reading or writing any data, readers of
+/// `reader_path` use the columns of the `sample_path` parquet file and other
+/// readers use the schema of their file. All the failed pipelines are reported.
+pub fn check(input: &str, schemas: &[(PathBuf, PathBuf)]) -> Result<(), Error> {
+    let pipelines = parser::parse(input)?;
+    let mut ctx = if schemas.is_empty() {
+        None
+    } else {
+        Some(engine::Context::with_sample_schemas(schemas)?)
+    };
+
+    let mut errors = Vec::new();
+    for (idx, pipeline) in pipelines.iter().enumerate() {
+        let pipeline = slice::from_ref(pipeline);
+        let result = typing::validate(pipeline)
+            .map_err(Error::from)
+            .and_then(|_| match ctx.as_mut() {
+                Some(ctx) => Ok(engine::eval(ctx, pipeline)?),
+                None => Ok(()),
+            });
+
+        if let Err(e) = result {
+            if pipelines.len() > 1 {
+                let message = format!("in pipeline {}: {e}", idx + 1);
+                errors.push(e.with_message(message));
+            } else {
+                errors.push(e);
+            }
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(Error::CheckErrors(errors)),
+    }
+}
+
+/// Parses and validates a dply script.
+fn parse(input: &str) -> Result<Vec<Expr>, Error> {
     let pipelines = parser::parse(input)?;
     typing::validate(&pipelines)?;
-    engine::eval_to_string(&pipelines)
+    Ok(pipelines)
 }
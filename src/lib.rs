@@ -4,6 +4,7 @@
 //! Data manipulation tool inspired by the [dplyr](https://dplyr.tidyverse.org/) grammar.
 #![warn(clippy::all, rust_2018_idioms, missing_docs)]
 
+pub mod error;
 pub mod interpreter;
 pub mod repl;
 
@@ -12,5 +13,6 @@ mod config;
 mod engine;
 mod fuzzy;
 mod parser;
+mod schemas;
 mod signatures;
 mod typing;
@@ -13,7 +13,9 @@ use std::fs;
 use std::io::{self, IsTerminal, Read};
 use std::path::PathBuf;
 
-use dply::{interpreter, repl};
+use dply::error::Error;
+use dply::interpreter::{self, OutputFormat};
+use dply::repl;
 
 /// Cli interface.
 #[derive(Parser)]
@@ -25,24 +27,91 @@ pub struct Cli {
     /// dply command passed as string.
     #[arg(long, short)]
     pub command: Option<String>,
+
+    /// Format used to show dataframes.
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Don't show the interactive client welcome banner.
+    #[arg(long, short)]
+    pub quiet: bool,
+
+    /// Check the script functions and arguments without running it.
+    #[arg(long)]
+    pub check: bool,
+
+    /// Sample parquet file used by `--check` to validate the column names of a
+    /// reader, as `reader_path=sample.parquet`, can be repeated.
+    #[arg(long, requires = "check", value_parser = parse_schema)]
+    pub schema: Vec<(PathBuf, PathBuf)>,
+
+    /// Write errors to standard error as JSON with their line and column.
+    #[arg(long)]
+    pub json_errors: bool,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    if let Some(input) = cli.command {
-        interpreter::eval(&input)?;
+    if cli.json_errors {
+        if let Err(e) = run(cli) {
+            eprintln!("{}", json_error(&e));
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
+    run(cli)
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let input = if let Some(input) = cli.command {
+        input
     } else if let Some(path) = cli.path {
-        let input = fs::read_to_string(&path)
-            .map_err(|e| anyhow!("Error reading script {}: {e}", path.display()))?;
-        interpreter::eval(&input)?;
+        fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Error reading script {}: {e}", path.display()))?
     } else if io::stdin().is_terminal() {
-        repl::run()?;
+        let options = repl::Options {
+            quiet: cli.quiet,
+            format: cli.format,
+        };
+        return repl::run_with_options(&options);
     } else {
         let mut input = String::new();
         io::stdin().read_to_string(&mut input)?;
-        interpreter::eval(&input)?;
+        input
     };
 
+    if cli.check {
+        interpreter::check(&input, &cli.schema)?;
+    } else {
+        interpreter::eval_with_format(&input, cli.format)?;
+    }
+
     Ok(())
 }
+
+/// Parses a `reader_path=sample.parquet` schema option.
+fn parse_schema(value: &str) -> Result<(PathBuf, PathBuf), String> {
+    match value.rsplit_once('=') {
+        Some((reader, sample)) if !reader.is_empty() && !sample.is_empty() => {
+            Ok((reader.into(), sample.into()))
+        }
+        _ => Err(format!(
+            "expected reader_path=sample.parquet, found '{value}'"
+        )),
+    }
+}
+
+/// Formats an error as a JSON object, the line and column are null for errors
+/// that don't have a position in the script.
+fn json_error(err: &anyhow::Error) -> serde_json::Value {
+    let position = err.downcast_ref::<Error>().and_then(Error::position);
+
+    serde_json::json!({
+        "error": err.to_string(),
+        "line": position.map(|p| p.line),
+        "column": position.map(|p| p.column),
+    })
+}
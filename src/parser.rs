@@ -5,8 +5,8 @@
 use anyhow::{bail, Result};
 use nom::branch::alt;
 use nom::bytes::complete::{is_a, is_not, tag};
-use nom::character::complete::{alpha1, alphanumeric1, char, multispace0, newline};
-use nom::combinator::{cut, map, recognize, value, verify};
+use nom::character::complete::{alpha1, alphanumeric1, char, digit1, multispace0, newline, one_of};
+use nom::combinator::{cut, map, map_opt, opt, recognize, rest_len, value, verify};
 use nom::error::{context, convert_error, VerboseError};
 use nom::multi::{many0, many0_count, many1_count, separated_list0, separated_list1};
 use nom::number::complete::double;
@@ -14,10 +14,13 @@ use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::IResult;
 use std::fmt;
 
+use crate::error::{Error, Position};
+
 /// A parsed dply expression.
 pub enum Expr {
-    /// A pipeline of data manipulation expressions.
-    Pipeline(Vec<Expr>),
+    /// A pipeline of data manipulation expressions and the position of each
+    /// expression in the input.
+    Pipeline(Vec<Expr>, Vec<Position>),
     /// A function invocation.
     Function(String, Vec<Expr>),
     /// Binary operation
@@ -30,6 +33,8 @@ pub enum Expr {
     String(String),
     /// A number literal
     Number(f64),
+    /// A list literal
+    List(Vec<Expr>),
 }
 
 /// A binary operation.
@@ -94,7 +99,7 @@ impl fmt::Display for Operator {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Expr::Pipeline(exprs) => {
+            Expr::Pipeline(exprs, _) => {
                 for (idx, expr) in exprs.iter().enumerate() {
                     if idx > 0 {
                         write!(f, " | ")?;
@@ -125,6 +130,16 @@ impl fmt::Display for Expr {
             Expr::Identifier(n) => write!(f, "{n}"),
             Expr::String(s) => write!(f, r#""{s}""#),
             Expr::Number(n) => write!(f, "{n}"),
+            Expr::List(exprs) => {
+                write!(f, "[")?;
+                for (idx, expr) in exprs.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    expr.fmt(f)?;
+                }
+                write!(f, "]")
+            }
         }
     }
 }
@@ -143,7 +158,7 @@ fn fmt_debug(expr: &Expr, indent: usize, f: &mut fmt::Formatter<'_>) -> fmt::Res
     }
 
     match expr {
-        Expr::Pipeline(exprs) => {
+        Expr::Pipeline(exprs, _) => {
             windent!(f, "pre_pipeline")?;
             for expr in exprs {
                 fmt_debug(expr, indent + 2, f)?;
@@ -171,6 +186,13 @@ fn fmt_debug(expr: &Expr, indent: usize, f: &mut fmt::Formatter<'_>) -> fmt::Res
         Expr::Identifier(id) => windent!(f, "identifier: {id}"),
         Expr::String(s) => windent!(f, "string: {s}"),
         Expr::Number(n) => windent!(f, "number: {n}"),
+        Expr::List(exprs) => {
+            windent!(f, "pre_list({})", exprs.len())?;
+            for expr in exprs {
+                fmt_debug(expr, indent + 2, f)?;
+            }
+            windent!(f, "post_list({})", exprs.len())
+        }
     }
 }
 
@@ -202,10 +224,45 @@ fn quoted(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 }
 
 fn string(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
-    let literal = verify(is_not("\""), |s: &str| !s.is_empty());
     map(
-        preceded(char('"'), cut(terminated(literal, char('"')))),
-        |s: &str| Expr::String(s.to_string()),
+        preceded(char('"'), cut(terminated(opt(is_not("\"")), char('"')))),
+        |s: Option<&str>| Expr::String(s.unwrap_or_default().to_string()),
+    )(input)
+}
+
+/// A number literal, digits can be separated by underscores as in `1_000_000`.
+fn number(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+    let digits = || recognize(pair(digit1, many0_count(preceded(char('_'), digit1))));
+    let literal = recognize(tuple((
+        digits(),
+        opt(pair(char('.'), opt(digits()))),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+    )));
+
+    map(
+        alt((
+            map_opt(literal, |s: &str| s.replace('_', "").parse().ok()),
+            double,
+        )),
+        Expr::Number,
+    )(input)
+}
+
+/// A list of expressions `[1, 2, 3]` or `["jan.parquet", "feb.parquet"]`.
+fn list(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+    context(
+        "list",
+        map(
+            delimited(
+                char('['),
+                separated_list0(
+                    preceded(multispace0, char(',')),
+                    preceded(multispace0, expression),
+                ),
+                cut(preceded(multispace0, char(']'))),
+            ),
+            Expr::List,
+        ),
     )(input)
 }
 
@@ -230,13 +287,7 @@ fn expression(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
         preceded(
             ws,
             alt((
-                function,
-                unary_op,
-                quoted,
-                identifier,
-                string,
-                map(double, Expr::Number),
-                group,
+                function, unary_op, quoted, identifier, string, list, number, group,
             )),
         ),
     )(input)
@@ -251,8 +302,11 @@ fn unary_op(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 
     context(
         "unary_op",
-        map(pair(operator, expression), |(op, expr)| {
-            Expr::UnaryOp(op, Box::new(expr))
+        map(pair(operator, expression), |(op, expr)| match (op, expr) {
+            // Fold signed number literals.
+            (Operator::Minus, Expr::Number(n)) => Expr::Number(-n),
+            (Operator::Plus, Expr::Number(n)) => Expr::Number(n),
+            (op, expr) => Expr::UnaryOp(op, Box::new(expr)),
         }),
     )(input)
 }
@@ -328,7 +382,10 @@ fn assign_op(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
             tuple((
                 preceded(multispace0, alt((quoted, identifier))),
                 preceded(multispace0, map(tag("="), |_| Operator::Assign)),
-                preceded(multispace0, alt((arith_op, expression))),
+                preceded(
+                    multispace0,
+                    alt((logical_op, compare_op, arith_op, expression)),
+                ),
             )),
             |(lhs, op, rhs)| Expr::BinaryOp(Box::new(lhs), op, Box::new(rhs)),
         ),
@@ -348,9 +405,16 @@ fn argument(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
 }
 
 fn function(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+    // Arguments can be followed by a trailing comma.
     let args = delimited(
         char('('),
-        separated_list0(preceded(multispace0, char(',')), argument),
+        map(
+            opt(terminated(
+                separated_list1(preceded(multispace0, char(',')), argument),
+                opt(preceded(multispace0, char(','))),
+            )),
+            Option::unwrap_or_default,
+        ),
         cut(preceded(multispace0, char(')'))),
     );
 
@@ -365,41 +429,97 @@ fn function(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
     )(input)
 }
 
+/// Pipeline expressions with the input length left before each one.
+type Steps = Vec<(usize, Expr)>;
+
 /// Parses a pipeline.
 ///
-/// A pipeline can be a list of function calls or identifiers separated by a pipe.
-fn pipeline(input: &str) -> IResult<&str, Expr, VerboseError<&str>> {
+/// A pipeline can be a list of function calls or identifiers separated by a pipe,
+/// the input length left is converted to a position once the whole input has
+/// been parsed.
+fn pipeline(input: &str) -> IResult<&str, Steps, VerboseError<&str>> {
     let separator = tuple((multispace0, tag("|"), multispace0));
 
     context(
         "pipeline",
-        map(
-            separated_list0(separator, cut(alt((function, identifier)))),
-            Expr::Pipeline,
-        ),
+        separated_list0(separator, pair(rest_len, cut(alt((function, identifier))))),
     )(input)
 }
 
 /// Parses one or more pipelines.
-fn root(input: &str) -> IResult<&str, Vec<Expr>, VerboseError<&str>> {
+fn root(input: &str) -> IResult<&str, Vec<Steps>, VerboseError<&str>> {
     let separator = alt((char(';'), newline));
     separated_list1(many1_count(separator), cut(pipeline))(input)
 }
 
+/// Removes a trailing `# comment` from a line, ignoring `#` in strings and quoted
+/// identifiers.
+fn strip_comment(line: &str) -> &str {
+    let mut delimiter = None;
+    for (idx, c) in line.char_indices() {
+        match (delimiter, c) {
+            (None, '#') => return &line[..idx],
+            (None, '"' | '`') => delimiter = Some(c),
+            (Some(d), c) if d == c => delimiter = None,
+            _ => {}
+        }
+    }
+
+    line
+}
+
 /// Parses one or more dply pipelines.
 pub fn parse(input: &str) -> Result<Vec<Expr>> {
+    // Comment lines are kept empty so that positions match the input.
     let input = input
         .lines()
-        .filter(|line| comment(line).is_err())
-        .map(|line| line.to_string() + "\n")
+        .map(|line| match comment(line) {
+            Ok(_) => "\n".to_string(),
+            Err(_) => strip_comment(line).to_string() + "\n",
+        })
         .collect::<String>();
 
-    match root(input.trim().trim_end_matches(';')) {
-        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
-            bail!("Parse error: {}", convert_error(input.as_str(), e))
+    let text = input.trim().trim_end_matches(';');
+
+    // Returns the position of text with `rest` remaining input.
+    let start = input.len() - input.trim_start().len();
+    let position = |rest: usize| {
+        let offset = start + text.len() - rest;
+        let line_start = input[..offset].rfind('\n').map_or(0, |p| p + 1);
+        Position {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+        }
+    };
+
+    match root(text) {
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => bail!(Error::ParseError {
+            message: format!("Parse error: {}", convert_error(input.as_str(), e.clone())),
+            position: e.errors.first().map(|(rest, _)| position(rest.len())),
+        }),
+        Err(e) => bail!(Error::ParseError {
+            message: format!("Parse error: {e}"),
+            position: None,
+        }),
+        Ok((_, pipelines)) => {
+            // Converts the remaining input lengths to positions, skipping the
+            // blanks before each expression.
+            let pipelines = pipelines
+                .into_iter()
+                .map(|steps| {
+                    let (positions, exprs) = steps
+                        .into_iter()
+                        .map(|(rest, expr)| {
+                            let rest = text[text.len() - rest..].trim_start_matches([' ', '\t']);
+                            (position(rest.len()), expr)
+                        })
+                        .unzip();
+                    Expr::Pipeline(exprs, positions)
+                })
+                .collect();
+
+            Ok(pipelines)
         }
-        Err(e) => bail!("Parse error: {e}"),
-        Ok((_, exprs)) => Ok(exprs),
     }
 }
 
@@ -672,6 +792,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn signed_numbers() {
+        // Signed number literals are folded, other unary minus operands are kept.
+        let text = indoc! {r#"
+            mutate(offset = -1.5 - time, scale = +2 * -time, diff = time - -1)
+        "#};
+
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: mutate(3)
+                    pre_binary_op: Assign
+                      identifier: offset
+                      pre_binary_op: Minus
+                        number: -1.5
+                        identifier: time
+                      post_binary_op: Minus
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: scale
+                      pre_binary_op: Multiply
+                        number: 2
+                        pre_unary_op: Minus
+                          identifier: time
+                        post_unary_op: Minus
+                      post_binary_op: Multiply
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: diff
+                      pre_binary_op: Minus
+                        identifier: time
+                        number: -1
+                      post_binary_op: Minus
+                    post_binary_op: Assign
+                  post_function: mutate(3)
+                post_pipeline"
+            )
+        );
+    }
+
+    #[test]
+    fn underscore_numbers() {
+        let text = indoc! {r#"
+            mutate(
+                a = 1_000_000,
+                b = 1_234.5_6,
+                c = 3.6e12,
+                d = 2_500e-3,
+                e = [1_000, -2_000]
+            )
+        "#};
+
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: mutate(5)
+                    pre_binary_op: Assign
+                      identifier: a
+                      number: 1000000
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: b
+                      number: 1234.56
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: c
+                      number: 3600000000000
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: d
+                      number: 2.5
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: e
+                      pre_list(2)
+                        number: 1000
+                        number: -2000
+                      post_list(2)
+                    post_binary_op: Assign
+                  post_function: mutate(5)
+                post_pipeline"
+            )
+        );
+
+        assert!(parse("mutate(a = 1__000)").is_err());
+        assert!(parse("mutate(a = 1_000_)").is_err());
+    }
+
     #[test]
     fn select_columns_and_rename() {
         let text = indoc! {r#"
@@ -811,6 +1023,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mutate_bool() {
+        let text = "mutate(flag = a > 20 & b == true, c = false)";
+
+        assert_parser!(
+            text,
+            indoc!(
+                r#"
+                pre_pipeline
+                  pre_function: mutate(2)
+                    pre_binary_op: Assign
+                      identifier: flag
+                      pre_binary_op: And
+                        pre_binary_op: Gt
+                          identifier: a
+                          number: 20
+                        post_binary_op: Gt
+                        pre_binary_op: Eq
+                          identifier: b
+                          identifier: true
+                        post_binary_op: Eq
+                      post_binary_op: And
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: c
+                      identifier: false
+                    post_binary_op: Assign
+                  post_function: mutate(2)
+                post_pipeline"#
+            )
+        );
+    }
+
+    #[test]
+    fn empty_string() {
+        let text = indoc! {r#"
+            mutate(label = str_c(a, "", b)) | filter(label != "")
+        "#};
+
+        assert_parser!(
+            text,
+            indoc!(
+                r#"
+                pre_pipeline
+                  pre_function: mutate(1)
+                    pre_binary_op: Assign
+                      identifier: label
+                      pre_function: str_c(3)
+                        identifier: a
+                        string: 
+                        identifier: b
+                      post_function: str_c(3)
+                    post_binary_op: Assign
+                  post_function: mutate(1)
+                  pre_function: filter(1)
+                    pre_binary_op: NotEq
+                      identifier: label
+                      string: 
+                    post_binary_op: NotEq
+                  post_function: filter(1)
+                post_pipeline"#
+            )
+        );
+
+        // An unterminated string is still an error.
+        assert!(parse(r#"filter(label != ")"#).is_err());
+    }
+
+    #[test]
+    fn rename_replace() {
+        let text = indoc! {r#"
+            rename(replace("^tpep_", "trip_"), vendor_id = VendorID)
+        "#};
+
+        assert_parser!(
+            text,
+            indoc!(
+                r#"
+                pre_pipeline
+                  pre_function: rename(2)
+                    pre_function: replace(2)
+                      string: ^tpep_
+                      string: trip_
+                    post_function: replace(2)
+                    pre_binary_op: Assign
+                      identifier: vendor_id
+                      identifier: VendorID
+                    post_binary_op: Assign
+                  post_function: rename(2)
+                post_pipeline"#
+            )
+        );
+    }
+
     #[test]
     fn and_or_filter() {
         let text = indoc! {r#"
@@ -969,4 +1275,212 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn list() {
+        let text = r#"parquet(["jan.parquet", "feb.parquet"])"#;
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: parquet(1)
+                    pre_list(2)
+                      string: jan.parquet
+                      string: feb.parquet
+                    post_list(2)
+                  post_function: parquet(1)
+                post_pipeline"
+            )
+        );
+
+        let text = indoc! {r#"
+            csv([
+                "jan.csv",
+                "feb.csv"
+            ], overwrite = true)
+        "#};
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: csv(2)
+                    pre_list(2)
+                      string: jan.csv
+                      string: feb.csv
+                    post_list(2)
+                    pre_binary_op: Assign
+                      identifier: overwrite
+                      identifier: true
+                    post_binary_op: Assign
+                  post_function: csv(2)
+                post_pipeline"
+            )
+        );
+
+        let text = "f(x = [1, -2.5, 3], y = [a, `b c`], z = [])";
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: f(3)
+                    pre_binary_op: Assign
+                      identifier: x
+                      pre_list(3)
+                        number: 1
+                        number: -2.5
+                        number: 3
+                      post_list(3)
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: y
+                      pre_list(2)
+                        identifier: a
+                        identifier: b c
+                      post_list(2)
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: z
+                      pre_list(0)
+                      post_list(0)
+                    post_binary_op: Assign
+                  post_function: f(3)
+                post_pipeline"
+            )
+        );
+
+        assert!(parse("f([1, 2)").is_err());
+        assert!(parse("f([1 2])").is_err());
+    }
+
+    #[test]
+    fn trailing_comma() {
+        let text = indoc! {r#"
+            parquet("test.parquet") |
+              select(a, b,) |
+              mutate(
+                c = a + b,
+                d = a * 2,
+              ) |
+              summarize(
+                n = n(),
+                mean_c = mean(c),
+              )
+        "#};
+
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: parquet(1)
+                    string: test.parquet
+                  post_function: parquet(1)
+                  pre_function: select(2)
+                    identifier: a
+                    identifier: b
+                  post_function: select(2)
+                  pre_function: mutate(2)
+                    pre_binary_op: Assign
+                      identifier: c
+                      pre_binary_op: Plus
+                        identifier: a
+                        identifier: b
+                      post_binary_op: Plus
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: d
+                      pre_binary_op: Multiply
+                        identifier: a
+                        number: 2
+                      post_binary_op: Multiply
+                    post_binary_op: Assign
+                  post_function: mutate(2)
+                  pre_function: summarize(2)
+                    pre_binary_op: Assign
+                      identifier: n
+                      pre_function: n(0)
+                      post_function: n(0)
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: mean_c
+                      pre_function: mean(1)
+                        identifier: c
+                      post_function: mean(1)
+                    post_binary_op: Assign
+                  post_function: summarize(2)
+                post_pipeline"
+            )
+        );
+
+        assert!(parse("select(,)").is_err());
+        assert!(parse("select(a,,)").is_err());
+    }
+
+    #[test]
+    fn inline_comment() {
+        let text = indoc! {r##"
+            parquet("test#1.parquet") | # read the data
+              mutate(
+                a = 1, # first
+                # full line comment
+                `b#` = "#2" # second
+              ) |
+              glimpse() # done
+        "##};
+
+        assert_parser!(
+            text,
+            indoc!(
+                "
+                pre_pipeline
+                  pre_function: parquet(1)
+                    string: test#1.parquet
+                  post_function: parquet(1)
+                  pre_function: mutate(2)
+                    pre_binary_op: Assign
+                      identifier: a
+                      number: 1
+                    post_binary_op: Assign
+                    pre_binary_op: Assign
+                      identifier: b#
+                      string: #2
+                    post_binary_op: Assign
+                  post_function: mutate(2)
+                  pre_function: glimpse(0)
+                  post_function: glimpse(0)
+                post_pipeline"
+            )
+        );
+    }
+
+    #[test]
+    fn pipeline_positions() {
+        let text = indoc! {r#"
+            # Comment line.
+            parquet("test.parquet") | select(a) |
+
+              head(5)
+            parquet("test.parquet"); show()
+        "#};
+
+        let lines = parse(text)
+            .unwrap()
+            .into_iter()
+            .map(|expr| match expr {
+                Expr::Pipeline(_, positions) => positions
+                    .into_iter()
+                    .map(|p| (p.line, p.column))
+                    .collect::<Vec<_>>(),
+                _ => panic!("Unexpected expression {expr}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            [vec![(2, 1), (2, 27), (4, 3)], vec![(5, 1)], vec![(5, 26)]]
+        );
+    }
 }
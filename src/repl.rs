@@ -2,16 +2,40 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! REPL for dply expressions.
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use reedline::*;
+use std::io::{self, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
+use crate::config::OutputFormat;
 use crate::{engine, fuzzy, parser, signatures, typing};
 
+/// Options for the REPL.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Don't show the welcome banner.
+    pub quiet: bool,
+    /// Format used to show dataframes.
+    pub format: OutputFormat,
+}
+
 /// Runs a REPL for evaluation
 pub fn run() -> Result<()> {
+    run_with_options(&Options::default())
+}
+
+/// Runs a REPL for evaluation with the given options.
+///
+/// The welcome banner is not shown if `quiet` is set or if the standard output
+/// is not a terminal.
+pub fn run_with_options(options: &Options) -> Result<()> {
     let evaluator = Arc::new(Evaluator::default());
+    {
+        let mut ctx = evaluator.ctx.lock().unwrap();
+        ctx.set_paging(true);
+        ctx.set_output_format(options.format);
+    }
 
     const HISTORY_NAME: &str = ".dply_history";
 
@@ -47,11 +71,13 @@ pub fn run() -> Result<()> {
         .with_history(history)
         .with_edit_mode(edit_mode);
 
-    println!("Welcome to dply {}", env!("CARGO_PKG_VERSION"));
-    println!("Use Tab for completions, arrows to move around, and Enter for selection.");
-    println!("Enter twice with an empty line to execute the pipeline.");
-    println!("Read a file with 'parquet', 'json', or 'csv' to get columns completions.");
-    println!("For columns only completions start completions with a dot.");
+    if !options.quiet && io::stdout().is_terminal() {
+        println!("Welcome to dply {}", env!("CARGO_PKG_VERSION"));
+        println!("Use Tab for completions, arrows to move around, and Enter for selection.");
+        println!("Enter twice with an empty line to execute the pipeline.");
+        println!("Read a file with 'parquet', 'json', or 'csv' to get columns completions.");
+        println!("For columns only completions start completions with a dot.");
+    }
 
     let prompt = DefaultPrompt {
         left_prompt: DefaultPromptSegment::Empty,
@@ -63,7 +89,7 @@ pub fn run() -> Result<()> {
         match sig {
             Signal::Success(input) => {
                 if let Err(e) = evaluator.eval(&input) {
-                    println!("Error: {e}");
+                    println!("Error: {e:#}");
                 }
             }
             Signal::CtrlD | Signal::CtrlC => {
@@ -84,33 +110,64 @@ impl Evaluator {
             let pipelines = parser::parse(input)?;
             typing::validate(&pipelines)?;
 
+            // Name the failed pipeline when the input has more than one.
             let mut ctx = self.ctx.lock().unwrap();
-            engine::eval(&mut ctx, &pipelines)?;
+            for (idx, pipeline) in pipelines.iter().enumerate() {
+                let result = engine::eval(&mut ctx, std::slice::from_ref(pipeline));
+                if pipelines.len() > 1 {
+                    result.with_context(|| format!("in pipeline {}", idx + 1))?;
+                } else {
+                    result?;
+                }
+            }
         }
 
         Ok(())
     }
 
-    fn completions(&self, pattern: &str) -> Vec<String> {
+    /// Returns the completions for the pattern, `input` is the text before the
+    /// cursor used to complete the columns of files that have not been read.
+    fn completions(&self, pattern: &str, input: &str) -> Vec<String> {
         let ctx = self.ctx.lock().unwrap();
 
         // If pattern starts with a dot only complete columns and variables.
-        let mut completions = if pattern.starts_with('.') {
+        let mut names = if pattern.starts_with('.') {
             Vec::new()
         } else {
             signatures::completions(pattern)
         };
+        names.extend(ctx.vars());
+
+        let mut columns = ctx.completions().collect::<Vec<_>>();
+        columns.extend(ctx.file_completions(input));
+
+        // Only column names are matched with typos, prefix matches are shown
+        // first and typos only when nothing else matches.
+        let pattern = pattern.trim_start_matches('.');
+        let matcher = fuzzy::Matcher::new(pattern);
+        let columns_matcher = fuzzy::Matcher::with_trigrams(pattern);
+
+        let mut scored = names
+            .into_iter()
+            .filter_map(|s| matcher.score(&s).map(|score| (score, s)))
+            .chain(
+                columns
+                    .into_iter()
+                    .filter_map(|s| columns_matcher.score(&s).map(|score| (score, s))),
+            )
+            .map(|(score, s)| (score, s.to_lowercase(), s))
+            .collect::<Vec<_>>();
+
+        if scored
+            .iter()
+            .any(|(score, _, _)| *score < fuzzy::TYPO_SCORE)
+        {
+            scored.retain(|(score, _, _)| *score < fuzzy::TYPO_SCORE);
+        }
 
-        completions.extend(ctx.completions());
-        completions.extend(ctx.vars());
-
-        completions.sort();
-        completions.dedup();
-
-        let matcher = fuzzy::Matcher::new(pattern.trim_start_matches('.'));
-
-        completions.retain(|s| matcher.is_match(s));
-        completions
+        scored.sort();
+        scored.dedup_by(|(_, _, s1), (_, _, s2)| s1 == s2);
+        scored.into_iter().map(|(_, _, s)| s).collect()
     }
 }
 
@@ -139,7 +196,7 @@ impl Completer for CustomCompleter {
     fn complete(&mut self, line: &str, pos: usize) -> Vec<Suggestion> {
         let line = &line[..pos];
         let prefix_pos = line
-            .rfind(&[',', '|', ' ', '\t', '\n'])
+            .rfind([',', '|', ' ', '\t', '\n'])
             .map(|p| p + 1)
             .unwrap_or(0);
 
@@ -161,12 +218,12 @@ impl Completer for CustomCompleter {
                 .collect()
         } else {
             let prefix_pos = line
-                .rfind(&['(', ',', '|', ' ', '\t', '\n'])
+                .rfind(['(', ',', '|', ' ', '\t', '\n'])
                 .map(|p| p + 1)
                 .unwrap_or(0);
 
             self.evaluator
-                .completions(&line[prefix_pos..])
+                .completions(&line[prefix_pos..], line)
                 .into_iter()
                 .map(|value| Suggestion {
                     value,
@@ -241,13 +298,121 @@ fn read_dir(path: &Path, filter: &str) -> Result<Vec<String>> {
 }
 
 /// Accept multi line input separated by empty line or semicolon.
+///
+/// An empty line doesn't complete the input while there are unclosed
+/// parentheses or brackets so that blank lines can be used inside long calls, a
+/// semicolon always completes it.
 struct CustomValidator;
 
 impl Validator for CustomValidator {
     fn validate(&self, line: &str) -> ValidationResult {
         match line.chars().last() {
+            Some('\n') if has_open_brackets(line) => ValidationResult::Incomplete,
             Some('\n') | Some(';') | None => ValidationResult::Complete,
             _ => ValidationResult::Incomplete,
         }
     }
 }
+
+/// Returns true if the input has unclosed parentheses or brackets.
+///
+/// Brackets inside strings, quoted identifiers, and comments are ignored.
+fn has_open_brackets(input: &str) -> bool {
+    let mut depth = 0i32;
+
+    for line in input.lines() {
+        let mut quote = None;
+        for c in line.chars() {
+            match (quote, c) {
+                (Some(q), c) if q == c => quote = None,
+                (Some(_), _) => {}
+                (None, '#') => break,
+                (None, '"' | '`') => quote = Some(c),
+                (None, '(' | '[') => depth += 1,
+                (None, ')' | ']') => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    depth > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_open_brackets() {
+        let validator = CustomValidator;
+        let complete =
+            |input: &str| matches!(validator.validate(input), ValidationResult::Complete);
+
+        assert!(complete(""));
+        assert!(complete("parquet(\"a.parquet\") | show()\n"));
+        assert!(!complete("parquet(\"a.parquet\") | show()"));
+
+        // Empty lines inside a call don't complete the input.
+        assert!(!complete(
+            "parquet(\"a.parquet\") |\n  summarize(\n    n = n(),\n"
+        ));
+        assert!(complete(
+            "parquet(\"a.parquet\") |\n  summarize(\n    n = n()\n  )\n"
+        ));
+
+        // Brackets in strings, quoted identifiers, and comments are ignored.
+        assert!(complete("csv(\"a(.csv\") | select(`b[`)\n"));
+        assert!(complete("# select(\ncsv(\"a.csv\") | show()\n"));
+        assert!(complete("csv(\"a.csv\") | # select(\n  show()\n"));
+        assert!(!complete("csv(\"a#.csv\") |\n  select(\n"));
+
+        // A semicolon always completes the input.
+        assert!(complete("parquet(\"a.parquet\") | summarize(;"));
+    }
+
+    #[test]
+    fn complete_columns_with_typos() {
+        let evaluator = Evaluator::default();
+        evaluator
+            .eval("parquet(\"tests/data/nyctaxi.parquet\") | trips\n")
+            .unwrap();
+
+        // A close match hides the other columns with the same suffix.
+        assert_eq!(evaluator.completions("toal_amount", ""), ["total_amount"]);
+
+        // Typos only match column names.
+        let completions = evaluator.completions("totl_amuont", "");
+        assert_eq!(completions.first().unwrap(), "total_amount");
+        assert!(completions.iter().all(|c| c.ends_with("_amount")));
+
+        assert!(evaluator.completions("selcet", "").is_empty());
+    }
+
+    #[test]
+    fn eval_keeps_variables_on_failure() {
+        let evaluator = Evaluator::default();
+
+        let input = concat!(
+            "csv(\"tests/data/zones.csv\") | zones\n",
+            "csv(\"tests/data/zones.csv\") | select(Zone) | filter(Borough == \"Queens\") | queens\n",
+        );
+
+        let err = evaluator.eval(input).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "in pipeline 2: in step 3 (`filter`) at line 2: filter error: Unknown column 'Borough'"
+        );
+
+        // The variable from the first pipeline is usable in the next input.
+        let mut vars = evaluator.ctx.lock().unwrap().vars();
+        vars.sort();
+        assert_eq!(vars, ["zones"]);
+
+        evaluator
+            .eval("zones | count(Borough) | boroughs\n")
+            .unwrap();
+        let mut vars = evaluator.ctx.lock().unwrap().vars();
+        vars.sort();
+        assert_eq!(vars, ["boroughs", "zones"]);
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::{engine, parser, typing};
+
+/// Returns the columns read by a reader call like `parquet("a.parquet")`.
+type Loader = dyn Fn(&str) -> Result<Vec<String>> + Send + Sync;
+
+/// A cache of the columns of the files read in the repl input.
+///
+/// Schemas are loaded in a background thread so that completions don't block
+/// while reading large or slow files.
+pub struct SchemaCache {
+    loader: Arc<Loader>,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+    call_re: Regex,
+}
+
+/// The columns of a file read with the given options.
+struct Entry {
+    options: String,
+    /// None while the schema is loading.
+    columns: Option<Vec<String>>,
+}
+
+impl Default for SchemaCache {
+    fn default() -> Self {
+        Self::with_loader(read_columns)
+    }
+}
+
+impl SchemaCache {
+    /// Creates a cache that loads schemas with the given loader.
+    fn with_loader<F>(loader: F) -> Self
+    where
+        F: Fn(&str) -> Result<Vec<String>> + Send + Sync + 'static,
+    {
+        Self {
+            loader: Arc::new(loader),
+            entries: Default::default(),
+            // The start of a reader call at the start of a pipeline.
+            call_re: Regex::new(r#"(?m)(?:^|;)\s*((?:parquet|csv|json)\(\s*"([^"]+)")"#).unwrap(),
+        }
+    }
+
+    /// Returns the cached columns of the files read in the input text.
+    ///
+    /// Files that are not in the cache, or that are read with different
+    /// options, are loaded in the background and have no columns until the
+    /// load completes.
+    pub fn completions(&self, text: &str) -> Vec<String> {
+        let mut columns = Vec::new();
+
+        for caps in self.call_re.captures_iter(text) {
+            let start = caps.get(1).unwrap();
+
+            // Skip calls that are still being typed.
+            let Some(len) = args_len(&text[start.end()..]) else {
+                continue;
+            };
+
+            let call = &text[start.start()..start.end() + len + 1];
+            let path = &caps[2];
+            let options = text[start.end()..start.end() + len]
+                .trim_start_matches([',', ' '])
+                .trim();
+            columns.extend(self.columns(call, path, options));
+        }
+
+        columns
+    }
+
+    /// Returns the columns of a file, starting a load if needed.
+    fn columns(&self, call: &str, path: &str, options: &str) -> Vec<String> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(path) {
+            Some(entry) if entry.options == options => {
+                return entry.columns.clone().unwrap_or_default();
+            }
+            _ => {}
+        }
+
+        entries.insert(
+            path.to_string(),
+            Entry {
+                options: options.to_string(),
+                columns: None,
+            },
+        );
+
+        let loader = self.loader.clone();
+        let entries = self.entries.clone();
+        let (call, path, options) = (call.to_string(), path.to_string(), options.to_string());
+
+        std::thread::spawn(move || {
+            let columns = loader(&call);
+
+            // Discard the columns if the file has been read again with other
+            // options while loading, unreadable files are loaded again the
+            // next time as they may have been created or fixed.
+            let mut entries = entries.lock().unwrap();
+            if entries.get(&path).is_some_and(|e| e.options == options) {
+                match columns {
+                    Ok(columns) => entries.get_mut(&path).unwrap().columns = Some(columns),
+                    Err(_) => {
+                        entries.remove(&path);
+                    }
+                }
+            }
+        });
+
+        Vec::new()
+    }
+}
+
+/// Returns the length of the rest of a call arguments up to its closing
+/// parenthesis, or `None` if the call is not closed.
+///
+/// Parentheses in nested calls like `null_values("NA")` and in strings are
+/// skipped.
+fn args_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (idx, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(idx),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Evaluates a reader call to get its columns.
+fn read_columns(call: &str) -> Result<Vec<String>> {
+    let pipelines = parser::parse(call)?;
+    typing::validate(&pipelines)?;
+
+    let mut ctx = engine::Context::default();
+    engine::eval(&mut ctx, &pipelines)?;
+    Ok(ctx.completions().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    /// Calls completions until they are not empty or a timeout expires.
+    fn wait_completions(cache: &SchemaCache, text: &str) -> Vec<String> {
+        let start = Instant::now();
+        loop {
+            let columns = cache.completions(text);
+            if !columns.is_empty() || start.elapsed() > Duration::from_secs(5) {
+                return columns;
+            }
+
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn load_in_background() {
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let loader_calls = calls.clone();
+        let cache = SchemaCache::with_loader(move |call| {
+            loader_calls.lock().unwrap().push(call.to_string());
+
+            // A slow loader that waits to be released.
+            release_rx.lock().unwrap().recv()?;
+            Ok(vec![format!("{}_col", call.len())])
+        });
+
+        // Returns immediately while the schema is loading.
+        let text = "csv(\"a.csv\") |";
+        assert!(cache.completions(text).is_empty());
+        assert!(cache.completions(text).is_empty());
+
+        release_tx.send(()).unwrap();
+        assert_eq!(wait_completions(&cache, text), ["12_col"]);
+        assert_eq!(*calls.lock().unwrap(), ["csv(\"a.csv\")"]);
+
+        // Reading the same path with other options reloads the schema.
+        let text = "csv(\"a.csv\", delimiter = \";\") | select(";
+        assert!(cache.completions(text).is_empty());
+        release_tx.send(()).unwrap();
+        assert_eq!(wait_completions(&cache, text), ["29_col"]);
+
+        // Cached schemas are not loaded again.
+        assert_eq!(cache.completions(text), ["29_col"]);
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn reader_calls() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let loader_calls = calls.clone();
+        let cache = SchemaCache::with_loader(move |call| {
+            loader_calls.lock().unwrap().push(call.to_string());
+            Ok(Vec::new())
+        });
+
+        // Writers at the end of a pipeline are not loaded.
+        cache.completions("parquet(\"a.parquet\") | csv(\"b.csv\")\n  json(\"c.json\") |");
+        cache.completions("x | head(); parquet(\"d.parquet\", overwrite = true)");
+
+        // Nested calls and strings with parentheses are part of the call.
+        cache.completions("csv(\"e.csv\", null_values(\"NA\", \")\")) | select(");
+
+        // Calls that are not closed are still being typed.
+        cache.completions("csv(\"f.csv\", null_values(\"NA\") | ");
+
+        let start = Instant::now();
+        while calls.lock().unwrap().len() < 4 && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let mut calls = calls.lock().unwrap().clone();
+        calls.sort();
+        assert_eq!(
+            calls,
+            [
+                "csv(\"e.csv\", null_values(\"NA\", \")\"))",
+                "json(\"c.json\")",
+                "parquet(\"a.parquet\")",
+                "parquet(\"d.parquet\", overwrite = true)"
+            ]
+        );
+    }
+
+    #[test]
+    fn reload_failed_schemas() {
+        let calls = Arc::new(Mutex::new(0));
+
+        // The first load fails, like a file that doesn't exist yet.
+        let loader_calls = calls.clone();
+        let cache = SchemaCache::with_loader(move |_| {
+            let mut calls = loader_calls.lock().unwrap();
+            *calls += 1;
+            if *calls == 1 {
+                anyhow::bail!("file not found");
+            }
+            Ok(vec!["a".to_string()])
+        });
+
+        let text = "csv(\"a.csv\") |";
+        assert_eq!(wait_completions(&cache, text), ["a"]);
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn read_file_columns() {
+        let cache = SchemaCache::default();
+        let columns = wait_completions(&cache, "csv(\"tests/data/zones.csv\") |");
+        assert_eq!(columns.len(), 4);
+        assert!(columns.contains(&"Borough".to_string()));
+    }
+}
@@ -4,6 +4,8 @@
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
+use anyhow::anyhow;
+
 use crate::fuzzy;
 
 pub type SignaturesMap = HashMap<&'static str, Args>;
@@ -15,29 +17,55 @@ pub fn functions() -> &'static SignaturesMap {
         let mut signatures = HashMap::new();
 
         def_arrange(&mut signatures);
+        def_assert(&mut signatures);
         def_config(&mut signatures);
         def_count(&mut signatures);
+        def_count_rows(&mut signatures);
         def_csv(&mut signatures);
         def_distinct(&mut signatures);
+        def_excel(&mut signatures);
+        def_expect_schema(&mut signatures);
         def_filter(&mut signatures);
         def_glimpse(&mut signatures);
         def_group_by(&mut signatures);
         def_head(&mut signatures);
         def_joins(&mut signatures);
         def_json(&mut signatures);
+        def_lineage(&mut signatures);
         def_mutate(&mut signatures);
         def_parquet(&mut signatures);
+        def_pivot_count(&mut signatures);
         def_relocate(&mut signatures);
         def_rename(&mut signatures);
+        def_sample(&mut signatures);
         def_show(&mut signatures);
+        def_sqlite(&mut signatures);
         def_select(&mut signatures);
+        def_struct_fields(&mut signatures);
         def_summarize(&mut signatures);
+        def_top_n(&mut signatures);
         def_unnest(&mut signatures);
 
         signatures
     })
 }
 
+/// Returns the function name most similar to an unknown name, if any.
+pub fn did_you_mean(name: &str) -> Option<&'static str> {
+    fuzzy::Matcher::with_trigrams(name)
+        .closest(functions().keys().copied())
+        .first()
+        .copied()
+}
+
+/// Returns an unknown function error, suggesting a similar function name.
+pub fn unknown_function(name: &str) -> anyhow::Error {
+    match did_you_mean(name) {
+        Some(fname) => anyhow!("Unknown function: {name}, did you mean '{fname}'?"),
+        None => anyhow!("Unknown function: {name}"),
+    }
+}
+
 pub fn completions(pattern: &str) -> Vec<String> {
     static NAMES: OnceLock<Vec<String>> = OnceLock::new();
 
@@ -66,13 +94,9 @@ pub fn completions(pattern: &str) -> Vec<String> {
         names
     });
 
-    let matcher = fuzzy::Matcher::new(pattern);
-
-    names
-        .iter()
-        .filter(|s| matcher.is_match(s))
-        .map(|s| s.to_string())
-        .collect()
+    let mut completions = names.clone();
+    fuzzy::Matcher::new(pattern).rank(&mut completions);
+    completions
 }
 
 fn has_string_arg(name: &str) -> bool {
@@ -80,7 +104,7 @@ fn has_string_arg(name: &str) -> bool {
     // string parameter (e.g. filter(contains(name, "john"))).
     matches!(
         name,
-        "parquet" | "csv" | "json" | "starts_with" | "ends_with"
+        "parquet" | "csv" | "excel" | "json" | "sqlite" | "starts_with" | "ends_with" | "replace"
     )
 }
 
@@ -98,6 +122,11 @@ pub enum Args {
     OneThenMore(ArgType, ArgType),
     /// A function with a fixed number of arguments.
     Ordered(Vec<ArgType>),
+    /// A function with a fixed number of arguments followed by zero or more
+    /// arguments of the last type.
+    OrderedThenMore(Vec<ArgType>, ArgType),
+    /// A function whose arguments are all optional but must be in order.
+    Optional(Vec<ArgType>),
 }
 
 impl Args {
@@ -113,11 +142,17 @@ impl Args {
                 names.extend(first.names());
                 names.extend(rest.names());
             }
-            Args::Ordered(args) => {
+            Args::Ordered(args) | Args::Optional(args) => {
                 for arg in args {
                     names.extend(arg.names());
                 }
             }
+            Args::OrderedThenMore(args, rest) => {
+                for arg in args {
+                    names.extend(arg.names());
+                }
+                names.extend(rest.names());
+            }
             _ => {}
         }
 
@@ -144,6 +179,8 @@ pub enum ArgType {
     Function(&'static str, Box<Args>),
     /// An identifier expression.
     Identifier,
+    /// A list of arguments of the given type.
+    List(Box<ArgType>),
     /// A logical expression.
     Logical(Box<ArgType>),
     /// A named identifier.
@@ -156,6 +193,8 @@ pub enum ArgType {
     OneOf(Vec<ArgType>),
     /// A string argument.
     String,
+    /// A data type name like `f64` or `"datetime[ns]"`.
+    DataType,
 }
 
 impl ArgType {
@@ -179,6 +218,11 @@ impl ArgType {
         Self::Eq(lhs.into(), rhs.into())
     }
 
+    /// Creates a list type.
+    fn list(arg: ArgType) -> Self {
+        Self::List(arg.into())
+    }
+
     /// Creates a logical type (&, |).
     fn logical(arg: ArgType) -> Self {
         Self::Logical(arg.into())
@@ -222,6 +266,7 @@ impl ArgType {
                 names.push(name);
                 names.extend(args.names());
             }
+            ArgType::List(arg) => names.extend(arg.names()),
             ArgType::Logical(arg) => names.extend(arg.names()),
             ArgType::Named(name) => names.push(name.to_string()),
             ArgType::Negate(arg) => names.extend(arg.names()),
@@ -238,11 +283,40 @@ impl ArgType {
 }
 
 fn def_arrange(signatures: &mut SignaturesMap) {
+    let sort_args = Args::OneOrMore(ArgType::OneOf(vec![
+        ArgType::Identifier,
+        ArgType::function("ci", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function(
+            "desc",
+            Args::Ordered(vec![ArgType::OneOf(vec![
+                ArgType::Identifier,
+                ArgType::function("ci", Args::Ordered(vec![ArgType::Identifier])),
+            ])]),
+        ),
+    ]));
+
+    signatures.insert("arrange", sort_args.clone());
+    signatures.insert("arrange_within_group", sort_args);
+}
+
+fn def_assert(signatures: &mut SignaturesMap) {
+    let aggregate = ArgType::OneOf(vec![
+        ArgType::function("max", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("mean", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("median", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("min", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("n", Args::None),
+        ArgType::function("sd", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("sum", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("var", Args::Ordered(vec![ArgType::Identifier])),
+    ]);
+
+    // assert(n() > 0) or assert(!is_null(passenger_count))
     signatures.insert(
-        "arrange",
+        "assert",
         Args::OneOrMore(ArgType::OneOf(vec![
-            ArgType::Identifier,
-            ArgType::function("desc", Args::Ordered(vec![ArgType::Identifier])),
+            ArgType::compare(aggregate, ArgType::Number),
+            filter_predicate(),
         ])),
     );
 }
@@ -254,6 +328,9 @@ fn def_config(signatures: &mut SignaturesMap) {
             ArgType::assign(ArgType::Named("max_columns"), ArgType::Number),
             ArgType::assign(ArgType::Named("max_column_width"), ArgType::Number),
             ArgType::assign(ArgType::Named("max_table_width"), ArgType::Number),
+            ArgType::assign(ArgType::Named("max_output_rows"), ArgType::Number),
+            ArgType::assign(ArgType::Named("strict"), ArgType::Bool),
+            ArgType::assign(ArgType::Named("timezone"), ArgType::String),
         ])),
     );
 }
@@ -264,16 +341,27 @@ fn def_count(signatures: &mut SignaturesMap) {
         Args::ZeroOrMore(ArgType::OneOf(vec![
             ArgType::Identifier,
             ArgType::assign(ArgType::Named("sort"), ArgType::Bool),
+            ArgType::assign(ArgType::Named("prop"), ArgType::Bool),
+            ArgType::assign(ArgType::Named("cum_prop"), ArgType::Bool),
         ])),
     );
 }
 
+fn def_count_rows(signatures: &mut SignaturesMap) {
+    signatures.insert("count_rows", Args::None);
+}
+
 fn def_csv(signatures: &mut SignaturesMap) {
     signatures.insert(
         "csv",
         Args::OneThenMore(
-            ArgType::String,
-            ArgType::assign(ArgType::Named("overwrite"), ArgType::Bool),
+            ArgType::OneOf(vec![ArgType::String, ArgType::list(ArgType::String)]),
+            ArgType::OneOf(vec![
+                ArgType::assign(ArgType::Named("overwrite"), ArgType::Bool),
+                ArgType::assign(ArgType::Named("partition_by"), ArgType::Identifier),
+                ArgType::function("null_values", Args::OneOrMore(ArgType::String)),
+                ArgType::Identifier,
+            ]),
         ),
     );
 }
@@ -282,9 +370,40 @@ fn def_distinct(signatures: &mut SignaturesMap) {
     signatures.insert("distinct", Args::OneOrMore(ArgType::Identifier));
 }
 
+fn def_excel(signatures: &mut SignaturesMap) {
+    signatures.insert(
+        "excel",
+        Args::OneThenMore(
+            ArgType::String,
+            ArgType::OneOf(vec![
+                ArgType::assign(ArgType::Named("sheet"), ArgType::String),
+                ArgType::assign(ArgType::Named("header_row"), ArgType::Number),
+            ]),
+        ),
+    );
+}
+
+fn def_expect_schema(signatures: &mut SignaturesMap) {
+    signatures.insert(
+        "expect_schema",
+        Args::OneOrMore(ArgType::OneOf(vec![
+            ArgType::assign(ArgType::Named("exact"), ArgType::Bool),
+            ArgType::assign(ArgType::Identifier, ArgType::DataType),
+        ])),
+    );
+}
+
 fn def_filter(signatures: &mut SignaturesMap) {
+    signatures.insert("filter", Args::OneOrMore(filter_predicate()));
+}
+
+/// Returns the predicates accepted by `filter` and `assert`.
+fn filter_predicate() -> ArgType {
     let compare_args = ArgType::compare(
-        ArgType::Identifier,
+        ArgType::OneOf(vec![
+            ArgType::Identifier,
+            ArgType::function("len", Args::Ordered(vec![ArgType::Identifier])),
+        ]),
         ArgType::OneOf(vec![
             ArgType::Identifier,
             ArgType::Number,
@@ -302,36 +421,83 @@ fn def_filter(signatures: &mut SignaturesMap) {
         ]),
     );
 
+    let bound = ArgType::OneOf(vec![
+        ArgType::Number,
+        ArgType::String,
+        ArgType::function("dt", Args::Ordered(vec![ArgType::String])),
+    ]);
+
+    let between_fn = ArgType::function(
+        "between",
+        Args::Ordered(vec![ArgType::Identifier, bound.clone(), bound]),
+    );
+
     let is_null_fn = ArgType::function("is_null", Args::Ordered(vec![ArgType::Identifier]));
 
+    let is_empty_fn = ArgType::function("is_empty", Args::Ordered(vec![ArgType::Identifier]));
+
+    let is_weekend_fn = ArgType::function("is_weekend", Args::Ordered(vec![ArgType::Identifier]));
+
+    let is_business_day_fn = ArgType::function(
+        "is_business_day",
+        Args::OneThenMore(ArgType::Identifier, ArgType::String),
+    );
+
     let predicates = ArgType::OneOf(vec![
+        between_fn.clone(),
+        ArgType::negate(between_fn),
         contains_fn.clone(),
         ArgType::negate(contains_fn),
         is_null_fn.clone(),
         ArgType::negate(is_null_fn),
+        is_empty_fn.clone(),
+        ArgType::negate(is_empty_fn),
+        is_weekend_fn.clone(),
+        ArgType::negate(is_weekend_fn),
+        is_business_day_fn.clone(),
+        ArgType::negate(is_business_day_fn),
     ]);
 
-    let filter_arg = ArgType::OneOf(vec![compare_args, predicates]);
+    // A bare identifier filters on a boolean column.
+    let filter_arg = ArgType::OneOf(vec![compare_args, predicates, ArgType::Identifier]);
 
-    signatures.insert(
-        "filter",
-        Args::OneOrMore(ArgType::OneOf(vec![
-            filter_arg.clone(),
-            ArgType::logical(filter_arg),
-        ])),
-    );
+    ArgType::OneOf(vec![filter_arg.clone(), ArgType::logical(filter_arg)])
 }
 
 fn def_glimpse(signatures: &mut SignaturesMap) {
-    signatures.insert("glimpse", Args::None);
+    signatures.insert(
+        "glimpse",
+        Args::Optional(vec![
+            ArgType::Identifier,
+            ArgType::assign(ArgType::Named("format"), ArgType::String),
+        ]),
+    );
 }
 
 fn def_group_by(signatures: &mut SignaturesMap) {
-    signatures.insert("group_by", Args::OneOrMore(ArgType::Identifier));
+    let contains_fn = ArgType::function("contains", Args::Ordered(vec![ArgType::String]));
+    let ends_with_fn = ArgType::function("ends_with", Args::Ordered(vec![ArgType::String]));
+    let start_with_fn = ArgType::function("starts_with", Args::Ordered(vec![ArgType::String]));
+
+    signatures.insert(
+        "group_by",
+        Args::OneOrMore(ArgType::OneOf(vec![
+            ArgType::Identifier,
+            contains_fn.clone(),
+            ArgType::negate(contains_fn),
+            ends_with_fn.clone(),
+            ArgType::negate(ends_with_fn),
+            start_with_fn.clone(),
+            ArgType::negate(start_with_fn),
+        ])),
+    );
 }
 
 fn def_head(signatures: &mut SignaturesMap) {
-    signatures.insert("head", Args::NoneOrOne(ArgType::Number));
+    signatures.insert(
+        "head",
+        Args::Optional(vec![ArgType::Identifier, ArgType::Number]),
+    );
 }
 
 fn def_joins(signatures: &mut SignaturesMap) {
@@ -340,9 +506,30 @@ fn def_joins(signatures: &mut SignaturesMap) {
         ArgType::eq(ArgType::Identifier, ArgType::Identifier),
     );
 
+    let coalesce_keys = ArgType::assign(ArgType::Named("coalesce_keys"), ArgType::Bool);
+
+    signatures.insert("anti_filter", args.clone());
     signatures.insert("anti_join", args.clone());
-    signatures.insert("cross_join", args.clone());
-    signatures.insert("inner_join", args.clone());
+    signatures.insert("cross_join", args);
+    signatures.insert(
+        "inner_join",
+        Args::OneThenMore(
+            ArgType::Identifier,
+            ArgType::OneOf(vec![
+                ArgType::compare(ArgType::Identifier, ArgType::Identifier),
+                coalesce_keys.clone(),
+            ]),
+        ),
+    );
+
+    let args = Args::OneThenMore(
+        ArgType::Identifier,
+        ArgType::OneOf(vec![
+            ArgType::eq(ArgType::Identifier, ArgType::Identifier),
+            coalesce_keys,
+        ]),
+    );
+
     signatures.insert("left_join", args.clone());
     signatures.insert("outer_join", args);
 }
@@ -354,17 +541,33 @@ fn def_json(signatures: &mut SignaturesMap) {
             ArgType::String,
             ArgType::OneOf(vec![
                 ArgType::assign(ArgType::Named("overwrite"), ArgType::Bool),
+                ArgType::assign(ArgType::Named("path"), ArgType::String),
                 ArgType::assign(ArgType::Named("schema_rows"), ArgType::Number),
             ]),
         ),
     );
 }
 
+fn def_lineage(signatures: &mut SignaturesMap) {
+    signatures.insert("lineage", Args::None);
+}
+
 fn def_mutate(signatures: &mut SignaturesMap) {
+    let rolling = |name| {
+        ArgType::function(
+            name,
+            Args::OrderedThenMore(
+                vec![ArgType::Identifier, ArgType::Number],
+                ArgType::assign(ArgType::Named("min_periods"), ArgType::Number),
+            ),
+        )
+    };
+
     let operand = ArgType::OneOf(vec![
         ArgType::Identifier,
         ArgType::Number,
         ArgType::String,
+        ArgType::Bool,
         ArgType::function("ymd_hms", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("dnanos", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("dmicros", Args::Ordered(vec![ArgType::Identifier])),
@@ -374,19 +577,167 @@ fn def_mutate(signatures: &mut SignaturesMap) {
         ArgType::function("micros", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("millis", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("secs", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("minutes", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("hours", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("days", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("epoch_seconds", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("from_epoch", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function(
+            "timezone_convert",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::String, ArgType::String]),
+        ),
+        // Alias of timezone_convert.
+        ArgType::function(
+            "convert_tz",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::String, ArgType::String]),
+        ),
+        ArgType::function(
+            "set_tz",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::String]),
+        ),
+        ArgType::function(
+            "normalize",
+            Args::OneThenMore(ArgType::Identifier, ArgType::Number),
+        ),
+        ArgType::function("standardize", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("fill_group", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function(
+            "fill_null",
+            Args::Ordered(vec![
+                ArgType::Identifier,
+                ArgType::assign(ArgType::Named("strategy"), ArgType::String),
+            ]),
+        ),
+        ArgType::function(
+            "cut",
+            Args::OrderedThenMore(
+                vec![ArgType::Identifier, ArgType::list(ArgType::Number)],
+                ArgType::assign(ArgType::Named("labels"), ArgType::list(ArgType::String)),
+            ),
+        ),
+        ArgType::function(
+            "qcut",
+            Args::OrderedThenMore(
+                vec![ArgType::Identifier, ArgType::Number],
+                ArgType::assign(ArgType::Named("labels"), ArgType::list(ArgType::String)),
+            ),
+        ),
+        ArgType::function(
+            "bin",
+            Args::OrderedThenMore(
+                vec![ArgType::Identifier, ArgType::Number, ArgType::Number],
+                ArgType::Number,
+            ),
+        ),
+        ArgType::function(
+            "concat_str",
+            Args::OneThenMore(
+                ArgType::Identifier,
+                ArgType::OneOf(vec![
+                    ArgType::Identifier,
+                    ArgType::assign(ArgType::Named("separator"), ArgType::String),
+                ]),
+            ),
+        ),
+        ArgType::function("format_string", Args::Ordered(vec![ArgType::String])),
+        ArgType::function(
+            "str_c",
+            Args::OneOrMore(ArgType::OneOf(vec![
+                ArgType::Identifier,
+                ArgType::String,
+                ArgType::assign(ArgType::Named("sep"), ArgType::String),
+                ArgType::assign(ArgType::Named("ignore_null"), ArgType::Bool),
+            ])),
+        ),
+        ArgType::function(
+            "coalesce",
+            Args::OneOrMore(ArgType::OneOf(vec![
+                ArgType::Identifier,
+                ArgType::Number,
+                ArgType::String,
+                ArgType::function("starts_with", Args::Ordered(vec![ArgType::String])),
+                ArgType::function("ends_with", Args::Ordered(vec![ArgType::String])),
+                ArgType::function("contains", Args::Ordered(vec![ArgType::String])),
+            ])),
+        ),
+        ArgType::function(
+            "str_pad",
+            Args::OrderedThenMore(vec![ArgType::Identifier, ArgType::Number], ArgType::String),
+        ),
+        ArgType::function(
+            "format_num",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Number]),
+        ),
+        ArgType::function(
+            "hash",
+            Args::OneThenMore(
+                ArgType::Identifier,
+                ArgType::OneOf(vec![
+                    ArgType::assign(ArgType::Named("algorithm"), ArgType::String),
+                    ArgType::assign(ArgType::Named("seed"), ArgType::Number),
+                ]),
+            ),
+        ),
+        ArgType::function("parse_int", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("parse_float", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function(
             "field",
             Args::Ordered(vec![ArgType::Identifier, ArgType::Identifier]),
         ),
         ArgType::function("len", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list_distinct", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list_max", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list_mean", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list_min", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list_sum", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function(
+            "list_concat",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Identifier]),
+        ),
+        ArgType::function(
+            "list_get",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Number]),
+        ),
+        ArgType::function(
+            "list_join",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::String]),
+        ),
+        ArgType::function(
+            "list_slice",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Number, ArgType::Number]),
+        ),
+        ArgType::function(
+            "list_sort",
+            Args::OneThenMore(
+                ArgType::Identifier,
+                ArgType::assign(ArgType::Named("descending"), ArgType::Bool),
+            ),
+        ),
+        ArgType::function(
+            "ntile",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Number]),
+        ),
         ArgType::function("max", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("mean", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("median", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("min", Args::Ordered(vec![ArgType::Identifier])),
         ArgType::function("row", Args::None),
+        rolling("rolling_mean"),
+        rolling("rolling_sum"),
+        rolling("rolling_min"),
+        rolling("rolling_max"),
     ]);
 
-    let expr = ArgType::OneOf(vec![operand.clone(), ArgType::arith(operand)]);
+    // Comparisons and logical operators create boolean columns.
+    let compare = ArgType::compare(operand.clone(), operand.clone());
+    let condition = ArgType::OneOf(vec![compare.clone(), ArgType::Identifier, ArgType::Bool]);
+
+    let expr = ArgType::OneOf(vec![
+        operand.clone(),
+        ArgType::arith(operand),
+        compare,
+        ArgType::logical(condition),
+    ]);
 
     signatures.insert(
         "mutate",
@@ -398,12 +749,23 @@ fn def_parquet(signatures: &mut SignaturesMap) {
     signatures.insert(
         "parquet",
         Args::OneThenMore(
-            ArgType::String,
-            ArgType::assign(ArgType::Named("overwrite"), ArgType::Bool),
+            ArgType::OneOf(vec![ArgType::String, ArgType::list(ArgType::String)]),
+            ArgType::OneOf(vec![
+                ArgType::assign(ArgType::Named("overwrite"), ArgType::Bool),
+                ArgType::assign(ArgType::Named("partition_by"), ArgType::Identifier),
+                ArgType::Identifier,
+            ]),
         ),
     );
 }
 
+fn def_pivot_count(signatures: &mut SignaturesMap) {
+    signatures.insert(
+        "pivot_count",
+        Args::Ordered(vec![ArgType::Identifier, ArgType::Identifier]),
+    );
+}
+
 fn def_relocate(signatures: &mut SignaturesMap) {
     signatures.insert(
         "relocate",
@@ -416,10 +778,32 @@ fn def_relocate(signatures: &mut SignaturesMap) {
 }
 
 fn def_rename(signatures: &mut SignaturesMap) {
+    let replace_fn = ArgType::function(
+        "replace",
+        Args::Ordered(vec![ArgType::String, ArgType::String]),
+    );
+
     signatures.insert(
         "rename",
-        Args::OneOrMore(ArgType::assign(ArgType::Identifier, ArgType::Identifier)),
+        Args::OneOrMore(ArgType::OneOf(vec![
+            ArgType::assign(ArgType::Identifier, ArgType::Identifier),
+            replace_fn,
+        ])),
     );
+
+    signatures.insert("reorder_like", Args::Ordered(vec![ArgType::Identifier]));
+}
+
+fn def_sample(signatures: &mut SignaturesMap) {
+    let args = || {
+        Args::OrderedThenMore(
+            vec![ArgType::Number],
+            ArgType::assign(ArgType::Named("seed"), ArgType::Number),
+        )
+    };
+
+    signatures.insert("sample_frac", args());
+    signatures.insert("sample_n", args());
 }
 
 fn def_select(signatures: &mut SignaturesMap) {
@@ -443,33 +827,87 @@ fn def_select(signatures: &mut SignaturesMap) {
 }
 
 fn def_summarize(signatures: &mut SignaturesMap) {
+    let compare_args = ArgType::compare(
+        ArgType::Identifier,
+        ArgType::OneOf(vec![
+            ArgType::Identifier,
+            ArgType::Number,
+            ArgType::String,
+            ArgType::Bool,
+            ArgType::function("dt", Args::Ordered(vec![ArgType::String])),
+        ]),
+    );
+
+    let predicate = ArgType::OneOf(vec![compare_args.clone(), ArgType::logical(compare_args)]);
+
+    let mut aggregates = vec![
+        ArgType::function("all", Args::Ordered(vec![predicate.clone()])),
+        ArgType::function("any", Args::Ordered(vec![predicate])),
+        ArgType::function("keep", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("list", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("max", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("mean", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("median", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("min", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("n", Args::None),
+        ArgType::function(
+            "quantile",
+            Args::Ordered(vec![ArgType::Identifier, ArgType::Number]),
+        ),
+        ArgType::function("sd", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("sum", Args::Ordered(vec![ArgType::Identifier])),
+        ArgType::function("var", Args::Ordered(vec![ArgType::Identifier])),
+    ];
+
+    // summarize(ratio = sum(a) / sum(b)), only numeric aggregates can be operands.
+    let mut operands: Vec<_> = aggregates
+        .iter()
+        .filter(|arg| !matches!(arg, ArgType::Function("all" | "any" | "keep" | "list", _)))
+        .cloned()
+        .collect();
+    operands.push(ArgType::Number);
+
+    aggregates.push(ArgType::arith(ArgType::OneOf(operands)));
+
     signatures.insert(
         "summarize",
         Args::OneOrMore(ArgType::Assign(
             Box::new(ArgType::Identifier),
-            Box::new(ArgType::OneOf(vec![
-                ArgType::function("list", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("max", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("mean", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("median", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("min", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("n", Args::None),
-                ArgType::function(
-                    "quantile",
-                    Args::Ordered(vec![ArgType::Identifier, ArgType::Number]),
-                ),
-                ArgType::function("sd", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("sum", Args::Ordered(vec![ArgType::Identifier])),
-                ArgType::function("var", Args::Ordered(vec![ArgType::Identifier])),
-            ])),
+            Box::new(ArgType::OneOf(aggregates)),
         )),
     );
 }
 
 fn def_show(signatures: &mut SignaturesMap) {
-    signatures.insert("show", Args::None);
+    signatures.insert("show", Args::NoneOrOne(ArgType::Identifier));
+}
+
+fn def_sqlite(signatures: &mut SignaturesMap) {
+    signatures.insert(
+        "sqlite",
+        Args::Ordered(vec![
+            ArgType::String,
+            ArgType::OneOf(vec![
+                ArgType::String,
+                ArgType::assign(ArgType::Named("query"), ArgType::String),
+            ]),
+        ]),
+    );
+}
+
+fn def_struct_fields(signatures: &mut SignaturesMap) {
+    signatures.insert("struct_fields", Args::Ordered(vec![ArgType::Identifier]));
+}
+
+fn def_top_n(signatures: &mut SignaturesMap) {
+    signatures.insert(
+        "top_n",
+        Args::Ordered(vec![ArgType::Number, ArgType::Identifier]),
+    );
 }
 
 fn def_unnest(signatures: &mut SignaturesMap) {
     signatures.insert("unnest", Args::OneOrMore(ArgType::Identifier));
+    signatures.insert("unnest_list", Args::OneOrMore(ArgType::Identifier));
+    signatures.insert("unnest_struct", Args::OneOrMore(ArgType::Identifier));
 }
@@ -4,15 +4,35 @@
 //! Checks pipeline functions and arguments types.
 use anyhow::{anyhow, bail, Result};
 
+use crate::error::Error;
 use crate::parser::{Expr, Operator};
 use crate::signatures::{self, ArgType, Args};
 
+/// An argument that has the right form but names an unknown type.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct UnknownType(String);
+
 /// Checks pipeline functions and arguments types.
+///
+/// Errors name the failed pipeline step and its line in the input.
 pub fn validate(exprs: &[Expr]) -> Result<()> {
     for expr in exprs {
-        if let Expr::Pipeline(exprs) = expr {
-            for expr in exprs {
-                check_signature(expr)?;
+        if let Expr::Pipeline(exprs, positions) = expr {
+            for (idx, (expr, position)) in exprs.iter().zip(positions).enumerate() {
+                let prev = idx.checked_sub(1).map(|idx| &exprs[idx]);
+                if let Err(e) = check_step(idx, expr, prev) {
+                    let name = match expr {
+                        Expr::Function(name, _) | Expr::Identifier(name) => name,
+                        _ => "?",
+                    };
+
+                    let err = Error::TypeError {
+                        message: e.to_string(),
+                        position: None,
+                    };
+                    bail!(err.in_step(idx + 1, name, *position));
+                }
             }
         }
     }
@@ -20,6 +40,31 @@ pub fn validate(exprs: &[Expr]) -> Result<()> {
     Ok(())
 }
 
+fn check_step(idx: usize, expr: &Expr, prev: Option<&Expr>) -> Result<()> {
+    match expr {
+        // A group must be consumed before grouping again.
+        Expr::Function(name, _)
+            if name == "group_by"
+                && matches!(prev, Some(Expr::Function(prev, _)) if prev == "group_by") =>
+        {
+            bail!("group_by error: must call summarize after a group_by");
+        }
+        // An identifier in the first step reads a variable, in later steps it
+        // assigns the input to a variable, names of functions are reserved so
+        // that `count` and `count()` are never confused.
+        Expr::Identifier(name) if signatures::functions().contains_key(name.as_str()) => {
+            if idx == 0 {
+                bail!("'{name}' is a function, not a variable, did you mean `{name}()`?");
+            } else {
+                bail!(
+                    "Cannot assign to variable '{name}', it is the name of the function `{name}`"
+                );
+            }
+        }
+        _ => check_signature(expr),
+    }
+}
+
 fn check_signature(expr: &Expr) -> Result<()> {
     match expr {
         Expr::Function(name, expr_args) => {
@@ -27,7 +72,7 @@ fn check_signature(expr: &Expr) -> Result<()> {
             if let Some(sig_args) = sigs.get(name.as_str()) {
                 check_args(name, expr_args, sig_args)
             } else {
-                Err(anyhow!("Unknown function: {name}"))
+                Err(signatures::unknown_function(name))
             }
         }
         Expr::Identifier(_) => Ok(()),
@@ -85,6 +130,38 @@ fn check_args(name: &str, exprs: &[Expr], sig_args: &Args) -> Result<()> {
                 check_arg(name, expr, arg)?;
             }
         }
+        signatures::Args::OrderedThenMore(args, rest) => {
+            if exprs.len() < args.len() {
+                bail!("Missing arguments for function '{name}'");
+            }
+
+            for (expr, arg) in exprs.iter().zip(args.iter()) {
+                check_arg(name, expr, arg)?;
+            }
+
+            for expr in &exprs[args.len()..] {
+                check_arg(name, expr, rest)?;
+            }
+        }
+        signatures::Args::Optional(args) => {
+            let mut args = args.iter();
+            for expr in exprs {
+                // Skip the optional arguments that don't match this expression.
+                let mut last_err = None;
+                loop {
+                    let Some(arg) = args.next() else {
+                        return Err(last_err.unwrap_or_else(|| {
+                            anyhow!("Too many arguments for function '{name}'")
+                        }));
+                    };
+
+                    match check_arg(name, expr, arg) {
+                        Ok(()) => break,
+                        Err(e) => last_err = Some(e),
+                    }
+                }
+            }
+        }
     };
 
     Ok(())
@@ -96,9 +173,11 @@ fn check_arg(fname: &str, expr: &Expr, arg: &ArgType) -> Result<()> {
         ArgType::Assign(lhs, rhs) => check_assign(fname, expr, lhs, rhs),
         ArgType::Bool => check_bool(fname, expr),
         ArgType::Compare(lhs, rhs) => check_compare(fname, expr, lhs, rhs),
+        ArgType::DataType => check_data_type(fname, expr),
         ArgType::Eq(lhs, rhs) => check_equal(fname, expr, lhs, rhs),
         ArgType::Function(name, args) => check_function(name, expr, args),
         ArgType::Identifier => check_identifier(fname, expr),
+        ArgType::List(arg) => check_list(fname, expr, arg),
         ArgType::Logical(arg) => check_logical(fname, expr, arg),
         ArgType::Named(name) => check_named(fname, name, expr),
         ArgType::Negate(arg) => check_negate(fname, expr, arg),
@@ -175,6 +254,44 @@ fn check_compare(fname: &str, expr: &Expr, larg: &ArgType, rarg: &ArgType) -> Re
     }
 }
 
+fn check_data_type(fname: &str, expr: &Expr) -> Result<()> {
+    let name = match expr {
+        Expr::Identifier(name) | Expr::String(name) => name,
+        _ => bail!("Invalid argument '{expr}' for function '{fname}'"),
+    };
+
+    if is_data_type(name) {
+        Ok(())
+    } else {
+        Err(UnknownType(format!("Unknown type '{name}' for function '{fname}'")).into())
+    }
+}
+
+/// Returns true if `name` is a type name as shown in tables, like `f64`,
+/// `list[str]`, or `datetime[ns, UTC]`.
+fn is_data_type(name: &str) -> bool {
+    const TYPES: &[&str] = &[
+        "binary", "bool", "cat", "date", "f32", "f64", "i8", "i16", "i32", "i64", "null", "str",
+        "time", "u8", "u16", "u32", "u64",
+    ];
+    const UNITS: &[&str] = &["ms", "μs", "ns"];
+
+    let Some((base, param)) = name.strip_suffix(']').and_then(|name| name.split_once('[')) else {
+        return TYPES.contains(&name);
+    };
+
+    match base {
+        "datetime" => match param.split_once(", ") {
+            Some((unit, tz)) => UNITS.contains(&unit) && !tz.is_empty(),
+            None => UNITS.contains(&param),
+        },
+        "duration" => UNITS.contains(&param),
+        "list" => is_data_type(param),
+        "array" | "decimal" | "struct" => !param.is_empty(),
+        _ => false,
+    }
+}
+
 fn check_equal(fname: &str, expr: &Expr, larg: &ArgType, rarg: &ArgType) -> Result<()> {
     match expr {
         Expr::BinaryOp(lhs, Operator::Eq, rhs) => {
@@ -200,6 +317,13 @@ fn check_identifier(fname: &str, expr: &Expr) -> Result<()> {
     }
 }
 
+fn check_list(fname: &str, expr: &Expr, arg: &ArgType) -> Result<()> {
+    match expr {
+        Expr::List(exprs) => exprs.iter().try_for_each(|e| check_arg(fname, e, arg)),
+        _ => Err(anyhow!("Invalid argument '{expr}' for function '{fname}'")),
+    }
+}
+
 fn check_logical(fname: &str, expr: &Expr, arg: &ArgType) -> Result<()> {
     fn is_logical(expr: &Expr) -> bool {
         matches!(
@@ -250,13 +374,17 @@ fn check_number(fname: &str, expr: &Expr) -> Result<()> {
 }
 
 fn check_one_of(fname: &str, expr: &Expr, args: &[ArgType]) -> Result<()> {
+    let mut unknown_type = None;
     for arg in args {
-        if check_arg(fname, expr, arg).is_ok() {
-            return Ok(());
+        match check_arg(fname, expr, arg) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.is::<UnknownType>() => unknown_type = Some(e),
+            Err(_) => {}
         }
     }
 
-    Err(anyhow!("Invalid argument '{expr}' for function '{fname}'"))
+    // An unknown type name is more useful than a generic error.
+    Err(unknown_type.unwrap_or_else(|| anyhow!("Invalid argument '{expr}' for function '{fname}'")))
 }
 
 fn check_string(fname: &str, expr: &Expr) -> Result<()> {
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn arrange() -> Result<()> {
@@ -74,3 +74,120 @@ fn arrange_desc() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn arrange_ignore_case() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/fruits.csv") |
+            arrange(ci(name)) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 2)
+            name|stock
+            str|i64
+            ---
+            apple|5
+            Avocado|1
+            Banana|2
+            blueberry|4
+            cherry|7
+            Zebra|3
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/fruits.csv") |
+            arrange(desc(ci(name))) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 2)
+            name|stock
+            str|i64
+            ---
+            Zebra|3
+            cherry|7
+            blueberry|4
+            Banana|2
+            Avocado|1
+            apple|5
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/fruits.csv") |
+            arrange(ci(stock)) |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`arrange`) at line 2: arrange error: `ci` column 'stock' must be a string, found i64"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn arrange_within_group() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, total_amount) |
+            filter(total_amount > 60) |
+            group_by(payment_type) |
+            arrange_within_group(total_amount) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (13, 2)
+            payment_type|total_amount
+            str|f64
+            ---
+            Credit card|66.12
+            Credit card|66.36
+            Credit card|70.69
+            Credit card|74.2
+            Credit card|74.2
+            Credit card|74.22
+            Credit card|74.22
+            Credit card|77.6
+            Credit card|77.64
+            Credit card|84.36
+            Cash|61.85
+            Cash|61.85
+            Cash|63.1
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            arrange_within_group(total_amount) |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`arrange_within_group`) at line 2: arrange_within_group error: must be called after a group_by, use arrange"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn assert() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            assert(n() > 0, max(total_amount) < 10000, total_amount > -100) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            250
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn assert_aggregate_error() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            assert(max(total_amount) < 50)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`assert`) at line 2: assert error: `max(total_amount) < 50` failed, \
+        max(total_amount) is 84.36"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn assert_rows_error() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            assert(total_amount < 80)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`assert`) at line 2: assert error: `total_amount < 80` failed for 1 row, \
+        row 85: total_amount = 84.36"
+    );
+
+    // Null values fail the predicate, at most 5 rows are shown.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            assert(passenger_count > 0)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`assert`) at line 2: assert error: `passenger_count > 0` failed for 10 rows, \
+        row 28: passenger_count = null; row 89: passenger_count = null; \
+        row 96: passenger_count = null; row 148: passenger_count = null; \
+        row 167: passenger_count = null"
+    );
+
+    Ok(())
+}
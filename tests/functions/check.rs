@@ -0,0 +1,126 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use dply::error::Error;
+use dply::interpreter;
+use indoc::indoc;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn check_without_schema() {
+    let input = indoc! {r#"
+        parquet("missing.parquet") |
+            select(payment_type, total_amount) |
+            show()
+    "#};
+    assert!(interpreter::check(input, &[]).is_ok());
+
+    let err = interpreter::check(r#"parquet("a.parquet" | show()"#, &[]).unwrap_err();
+    assert!(matches!(err, Error::ParseError { .. }), "{err:?}");
+
+    let err = interpreter::check(r#"parquet("a.parquet") | head(5, bands)"#, &[]).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+    assert_eq!(
+        err.to_string(),
+        "in step 2 (`head`) at line 1: Too many arguments for function 'head'"
+    );
+}
+
+#[test]
+fn check_with_schema() {
+    let schema = &[(
+        PathBuf::from("missing.parquet"),
+        PathBuf::from("tests/data/nyctaxi.parquet"),
+    )];
+
+    // Readers use the sample schema, writers and asserts are skipped.
+    let input = indoc! {r#"
+        parquet("missing.parquet") |
+            select(payment_type, total_amount) |
+            assert(n() > 1000) |
+            csv("tests/data/check_output.csv") |
+            group_by(payment_type) |
+            summarize(total = sum(total_amount)) |
+            show()
+    "#};
+    assert!(interpreter::check(input, schema).is_ok());
+    assert!(!Path::new("tests/data/check_output.csv").exists());
+
+    let input = indoc! {r#"
+        parquet("missing.parquet") | select(totl_amount)
+    "#};
+    let err = interpreter::check(input, schema).unwrap_err();
+    assert!(matches!(err, Error::UnknownColumn { .. }), "{err:?}");
+
+    // All the failed pipelines are reported.
+    let input = indoc! {r#"
+        parquet("missing.parquet") | mutate(total = totl_amount * 2)
+        parquet("missing.parquet") | head(5, bands)
+        parquet("missing.parquet") | select(total_amount) | taxi_df
+        taxi_df | mutate(tip = tip_amount * 2)
+    "#};
+    let err = interpreter::check(input, schema).unwrap_err();
+    assert!(matches!(err, Error::CheckErrors(ref errors) if errors.len() == 3));
+    assert_eq!(
+        err.to_string(),
+        indoc!(
+            "
+            in pipeline 1: in step 2 (`mutate`) at line 1: mutate error: Unknown column 'totl_amount'; did you mean 'total_amount'?
+            in pipeline 2: in step 2 (`head`) at line 2: Too many arguments for function 'head'
+            in pipeline 4: in step 2 (`mutate`) at line 4: mutate error: Unknown column 'tip_amount'; did you mean 'total_amount'?"
+        )
+    );
+
+    let schema = &[(PathBuf::from("a.parquet"), PathBuf::from("missing.parquet"))];
+    let err = interpreter::check("parquet(\"a.parquet\")", schema).unwrap_err();
+    assert!(matches!(err, Error::IoError { .. }), "{err:?}");
+}
+
+#[test]
+fn check_with_schema_per_reader() {
+    let schema = &[(
+        PathBuf::from("missing.parquet"),
+        PathBuf::from("tests/data/nyctaxi.parquet"),
+    )];
+
+    // Readers without a sample schema use the schema of their file.
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") | zones
+        parquet("missing.parquet") |
+            inner_join(zones, PULocationID == LocationID) |
+            select(Zone, total_amount) |
+            show()
+    "#};
+    assert!(interpreter::check(input, schema).is_ok());
+
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") | select(total_amount)
+    "#};
+    let err = interpreter::check(input, schema).unwrap_err();
+    assert!(matches!(err, Error::UnknownColumn { .. }), "{err:?}");
+}
+
+#[test]
+fn check_reads_only_schema() {
+    // The type of the `id` column is inferred from the first 1000 rows, so the
+    // file fails to parse only when all its rows are read.
+    let path = std::env::temp_dir().join(format!("dply_check_{}.csv", std::process::id()));
+    let mut data = String::from("id\n");
+    for idx in 0..1000 {
+        data.push_str(&format!("{idx}\n"));
+    }
+    data.push_str("abc\n");
+    std::fs::write(&path, data).unwrap();
+
+    let input = format!("csv(\"{}\") | show()", path.display());
+    let result = interpreter::eval_to_string(&input);
+
+    let schema = &[(
+        PathBuf::from("missing.parquet"),
+        PathBuf::from("tests/data/nyctaxi.parquet"),
+    )];
+    let checked = interpreter::check(&input, schema);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(result.is_err());
+    assert!(checked.is_ok(), "{checked:?}");
+}
@@ -203,3 +203,68 @@ fn count_no_cols() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn count_sorted_ties() -> Result<()> {
+    // Equal counts are sorted by the group column.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(DOLocationID, sort = true) |
+            filter(n >= 8) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (10, 2)
+            DOLocationID|n
+            i64|u32
+            ---
+            237|11
+            161|10
+            236|10
+            48|9
+            132|9
+            140|9
+            142|9
+            79|8
+            163|8
+            230|8
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn count_prop() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(payment_type, sort = true, prop = true, cum_prop = true) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 4)
+            payment_type|n|prop|cum_prop
+            str|u32|f64|f64
+            ---
+            Credit card|185|0.74|0.74
+            Cash|53|0.212|0.952
+            Unknown|9|0.036|0.988
+            Dispute|2|0.008|0.996
+            No charge|1|0.004|1.0
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn count_rows() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            250
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/nyctaxi.csv") |
+            filter(total_amount > 50) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            20
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            count_rows()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`count_rows`) at line 3: count_rows error: must call summarize after a group_by"
+    );
+
+    Ok(())
+}
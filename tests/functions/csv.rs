@@ -0,0 +1,67 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::assert_interpreter;
+
+#[test]
+fn csv_null_values() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/nulls.csv", null_values("", "NA", "-")) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 4)
+            id|name|city|score
+            i64|str|str|f64
+            ---
+            1|Alice|Rome|3.5
+            2|null|Paris|null
+            3|null|null|4.0
+            4|Bob|null|null
+            5|null|null|2.0
+            6|Carol|Oslo|null
+            ---
+       "#
+        )
+    );
+
+    // Without null_values only empty unquoted fields are null.
+    let input = indoc! {r#"
+        csv("tests/data/nulls.csv") |
+            filter(is_null(name)) |
+            summarize(n = n()) |
+            show()
+        csv("tests/data/nulls.csv", null_values("", "NA")) |
+            filter(is_null(name)) |
+            summarize(n = n()) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            1
+            ---
+            shape: (1, 1)
+            n
+            u32
+            ---
+            3
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn df_variable() -> Result<()> {
@@ -58,3 +58,46 @@ fn df_variable() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn df_variable_function_name() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(passenger_count) |
+            count
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`count`) at line 3: Cannot assign to variable 'count', it is the name of the function `count`"
+    );
+
+    assert_interpreter_error!(
+        "show | head()",
+        "in step 1 (`show`) at line 1: 'show' is a function, not a variable, did you mean `show()`?"
+    );
+
+    // Variables that don't collide with functions still work.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(passenger_count) |
+            counts
+        counts | count() | show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            250
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
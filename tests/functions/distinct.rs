@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn distinct() -> Result<()> {
@@ -67,3 +67,80 @@ fn distinct() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn distinct_after_arrange() -> Result<()> {
+    // Payment types are in the order of their highest total amount.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            arrange(desc(total_amount)) |
+            distinct(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 1)
+            payment_type
+            str
+            ---
+            Credit card
+            Cash
+            Unknown
+            No charge
+            Dispute
+            ---
+        "#
+        )
+    );
+
+    // With more columns the first of the duplicate rows by sort order is kept.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            arrange(desc(total_amount)) |
+            distinct(payment_type, VendorID) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (8, 2)
+            payment_type|VendorID
+            str|i64
+            ---
+            Credit card|2
+            Credit card|1
+            Cash|2
+            Cash|1
+            Unknown|2
+            Unknown|1
+            No charge|1
+            Dispute|2
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn distinct_unknown_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            distinct(payment, VendorID)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`distinct`) at line 3: distinct error: Unknown column: payment; \
+         available: VendorID, payment_type, total_amount; did you mean 'payment_type'?"
+    );
+
+    Ok(())
+}
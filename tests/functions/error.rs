@@ -0,0 +1,227 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use dply::error::{Error, Position};
+use dply::interpreter;
+use indoc::indoc;
+
+#[test]
+fn error_parse() {
+    let err = interpreter::eval_to_string(r#"parquet("tests/data/nyctaxi.parquet""#).unwrap_err();
+    assert!(matches!(err, Error::ParseError { .. }), "{err:?}");
+    assert!(err.to_string().starts_with("Parse error:"));
+
+    let err =
+        interpreter::eval_to_string(r#"parquet("tests/data/nyctaxi.parquet", 1)"#).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+
+    let err = interpreter::eval_to_string(r#"parquet([1, 2])"#).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+
+    let err = interpreter::eval_to_string(r#"head(5, bands)"#).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+}
+
+#[test]
+fn error_unknown_column() {
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | select(totl_amount) | show()"#;
+    match interpreter::eval_to_string(input).unwrap_err() {
+        Error::UnknownColumn {
+            name,
+            suggestions,
+            message,
+            ..
+        } => {
+            assert_eq!(name, "totl_amount");
+            assert_eq!(
+                suggestions,
+                ["total_amount", "tolls_amount", "tip_amount", "fare_amount"]
+            );
+            assert!(message.starts_with(
+                "in step 2 (`select`) at line 1: select error: Unknown column: totl_amount;"
+            ));
+            assert!(message.ends_with("; did you mean 'total_amount'?"));
+        }
+        err => panic!("Unexpected error {err:?}"),
+    }
+
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | mutate(n = len(Fare_Amount))"#;
+    match interpreter::eval_to_string(input).unwrap_err() {
+        Error::UnknownColumn {
+            name,
+            suggestions,
+            message,
+            ..
+        } => {
+            assert_eq!(name, "Fare_Amount");
+            assert_eq!(
+                suggestions,
+                ["fare_amount", "tip_amount", "tolls_amount", "total_amount"]
+            );
+            assert_eq!(
+                message,
+                "in step 2 (`mutate`) at line 1: mutate error: Unknown column 'Fare_Amount'; did you mean 'fare_amount'?"
+            );
+        }
+        err => panic!("Unexpected error {err:?}"),
+    }
+}
+
+#[test]
+fn error_unknown_column_suggestion() {
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | filter(pasenger_count > 2)"#;
+    assert_eq!(
+        interpreter::eval_to_string(input).unwrap_err().to_string(),
+        "in step 2 (`filter`) at line 1: filter error: Unknown column 'pasenger_count'; did you mean 'passenger_count'?"
+    );
+
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | arrange(total_amout)"#;
+    assert_eq!(
+        interpreter::eval_to_string(input).unwrap_err().to_string(),
+        "in step 2 (`arrange`) at line 1: arrange error: Unknown column 'total_amout'; did you mean 'total_amount'?"
+    );
+
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | count(paymnt_type)"#;
+    assert_eq!(
+        interpreter::eval_to_string(input).unwrap_err().to_string(),
+        "in step 2 (`count`) at line 1: count error: Unknown column 'paymnt_type'; did you mean 'payment_type'?"
+    );
+
+    // No suggestion without a similar column.
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | filter(riders > 2)"#;
+    assert_eq!(
+        interpreter::eval_to_string(input).unwrap_err().to_string(),
+        "in step 2 (`filter`) at line 1: filter error: Unknown column 'riders'"
+    );
+}
+
+#[test]
+fn error_type_line() {
+    // Lines are counted in the input including blank and comment lines.
+    let input = indoc! {r#"
+
+        parquet("tests/data/nyctaxi.parquet") | head(2)
+
+        # Top trips.
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount) |
+            arrange(desc(total_amount)) | # largest first
+            head(5, rows)
+    "#};
+
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+    assert_eq!(
+        err.to_string(),
+        "in step 4 (`head`) at line 8: Too many arguments for function 'head'"
+    );
+}
+
+#[test]
+fn error_position() {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount |
+            show()
+    "#};
+
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::ParseError { .. }), "{err:?}");
+    // The missing parenthesis is reported at the end of the input.
+    assert_eq!(
+        err.position(),
+        Some(Position {
+            line: 3,
+            column: 11
+        })
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount) | head(5, rows)
+    "#};
+
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+    assert_eq!(
+        err.position(),
+        Some(Position {
+            line: 2,
+            column: 28
+        })
+    );
+
+    // Evaluation errors have the position of the failed step.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(totl_amount)
+    "#};
+
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::UnknownColumn { .. }), "{err:?}");
+    assert_eq!(err.position(), Some(Position { line: 2, column: 5 }));
+}
+
+#[test]
+fn error_unknown_function() {
+    let input = r#"parquet("tests/data/nyctaxi.parquet") | fliter(passenger_count > 1)"#;
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::TypeError { .. }), "{err:?}");
+    assert_eq!(
+        err.to_string(),
+        "in step 2 (`fliter`) at line 1: Unknown function: fliter, did you mean 'filter'?"
+    );
+
+    let err = interpreter::eval_to_string(
+        r#"parquet("tests/data/nyctaxi.parquet") | gruop_by(payment_type)"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "in step 2 (`gruop_by`) at line 1: Unknown function: gruop_by, did you mean 'group_by'?"
+    );
+
+    let err = interpreter::eval_to_string(r#"parquet("tests/data/nyctaxi.parquet") | xyz()"#)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "in step 2 (`xyz`) at line 1: Unknown function: xyz"
+    );
+}
+
+#[test]
+fn error_missing_file() {
+    let err = interpreter::eval_to_string(r#"parquet("tests/data/missing.parquet") | show()"#)
+        .unwrap_err();
+    assert!(matches!(err, Error::IoError { .. }), "{err:?}");
+
+    let err = interpreter::eval_to_string(r#"csv("tests/data/missing.csv") | show()"#).unwrap_err();
+    assert!(matches!(err, Error::IoError { .. }), "{err:?}");
+}
+
+#[test]
+fn error_pipeline_step() {
+    let input = r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(passenger_count, total_amount) |
+            filter(total_amount > 10) |
+            arrange(fare_amount) |
+            show()
+    "#;
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(matches!(err, Error::UnknownColumn { .. }), "{err:?}");
+    assert!(err
+        .to_string()
+        .starts_with("in step 4 (`arrange`) at line 5: arrange error: Unknown column"));
+
+    // Step numbers restart for each pipeline.
+    let input = r#"
+        parquet("tests/data/nyctaxi.parquet") | head(1)
+        parquet("tests/data/nyctaxi.parquet") | count(vendor)
+    "#;
+    let err = interpreter::eval_to_string(input).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("in step 2 (`count`) at line 3: "),
+        "{err}"
+    );
+}
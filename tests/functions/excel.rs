@@ -0,0 +1,96 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::assert_interpreter_error;
+
+#[cfg(feature = "excel")]
+use super::assert_interpreter;
+
+#[test]
+#[cfg(feature = "excel")]
+fn excel_header_row() -> Result<()> {
+    let input = indoc! {r#"
+        excel("tests/data/sales.xlsx", header_row = 2) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            region|units|price|date
+            str|i64|f64|datetime[ms]
+            ---
+            North|10|2.5|2023-01-15 00:00:00
+            South|7|3.25|2023-02-01 00:00:00
+            East|12|1.75|2023-03-10 00:00:00
+            West|null|4.0|2023-03-31 00:00:00
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"excel("tests/data/sales.xlsx", header_row = 9) | show()"#,
+        "in step 1 (`excel`) at line 1: excel error: header row 9 is outside the range A1:D6 of sheet 'sales'"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "excel")]
+fn excel_sheet() -> Result<()> {
+    let input = indoc! {r#"
+        excel("tests/data/sales.xlsx", sheet = "regions") |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 4)
+            region|manager|code|active
+            str|str|str|str
+            ---
+            North|Alice|101|true
+            South|Bob|S2|false
+            East|Carol|103|true
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"excel("tests/data/sales.xlsx", sheet = "products") | show()"#,
+        "in step 1 (`excel`) at line 1: excel error: sheet 'products' not found, \
+         available: sales, regions, errors"
+    );
+
+    assert_interpreter_error!(
+        r#"excel("tests/data/sales.xlsx", sheet = "errors") | show()"#,
+        "in step 1 (`excel`) at line 1: excel error: cannot read cell B3 of sheet 'errors': #DIV/0!"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "excel"))]
+fn excel_disabled() -> Result<()> {
+    let input = indoc! {r#"
+        excel("tests/data/sales.xlsx") |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 1 (`excel`) at line 1: excel error: dply was built without the excel feature"
+    );
+
+    Ok(())
+}
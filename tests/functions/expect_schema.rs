@@ -0,0 +1,118 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn expect_schema() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(trip_distance, payment_type, tpep_pickup_datetime) |
+            expect_schema(
+                trip_distance = f64,
+                payment_type = str,
+                tpep_pickup_datetime = "datetime[ns]",
+                exact = true
+            ) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 3)
+            trip_distance|payment_type|tpep_pickup_datetime
+            f64|str|datetime[ns]
+            ---
+            3.14|Credit card|2022-11-22 19:27:01
+            1.06|Cash|2022-11-27 16:43:26
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn expect_schema_errors() -> Result<()> {
+    // Missing column and wrong type.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            expect_schema(trip_distance = i64, tip = f64, payment_type = str)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`expect_schema`) at line 2: expect_schema error: schema mismatch: \
+        column 'trip_distance' is f64, expected i64; column 'tip' is missing"
+    );
+
+    // Extra columns with exact.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(trip_distance, payment_type, total_amount) |
+            expect_schema(trip_distance = f64, exact = true)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`expect_schema`) at line 3: expect_schema error: schema mismatch: \
+        column 'payment_type' is not expected; column 'total_amount' is not expected"
+    );
+
+    // Type names are checked before running the pipeline.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            expect_schema(trip_distance = f46)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`expect_schema`) at line 2: Unknown type 'f46' for function 'expect_schema'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn expect_schema_option_column() -> Result<()> {
+    // A column named like the option is checked by its type.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(exact = trip_distance) |
+            expect_schema(exact = f64, exact = true) |
+            head(1)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            exact
+            f64
+            ---
+            3.14
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(exact = trip_distance) |
+            expect_schema(exact = str)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`expect_schema`) at line 3: expect_schema error: schema mismatch: \
+        column 'exact' is f64, expected str"
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn filter_lt() -> Result<()> {
@@ -689,3 +689,467 @@ fn filter_is_not_null() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn filter_is_empty() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/nulls.csv") |
+            filter(is_empty(name) | is_empty(city)) |
+            show()
+        parquet("tests/data/lists.parquet") |
+            filter(!is_empty(ints)) |
+            summarize(n = n()) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 4)
+            id|name|city|score
+            i64|str|str|str
+            ---
+            2||Paris|NA
+            3|NA||4
+            ---
+            shape: (1, 1)
+            n
+            u32
+            ---
+            184
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"csv("tests/data/nulls.csv") | filter(is_empty(id))"#,
+        "in step 2 (`filter`) at line 1: filter error: Column 'id' must be a str or a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_is_weekend() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(tpep_pickup_datetime) |
+            filter(is_weekend(tpep_pickup_datetime) & tpep_pickup_datetime < dt("2022-11-07")) |
+            arrange(tpep_pickup_datetime) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 1)
+            tpep_pickup_datetime
+            datetime[ns]
+            ---
+            2022-11-05 00:30:37
+            2022-11-05 00:36:25
+            2022-11-05 02:01:05
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(!is_weekend(tpep_pickup_datetime)) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            169
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_is_business_day() -> Result<()> {
+    // Skip Thanksgiving and the following day.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(tpep_pickup_datetime) |
+            filter(
+                is_business_day(tpep_pickup_datetime, "2022-11-24", "2022-11-25") &
+                tpep_pickup_datetime > dt("2022-11-23 20:00:00") &
+                tpep_pickup_datetime < dt("2022-11-28 10:00:00")
+            ) |
+            arrange(tpep_pickup_datetime) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 1)
+            tpep_pickup_datetime
+            datetime[ns]
+            ---
+            2022-11-23 20:50:15
+            2022-11-28 07:44:09
+            2022-11-28 09:54:14
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_categorical_contains() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/categories.parquet") |
+            filter(contains(payment_type, "(?i:card)")) |
+            select(payment_type, zone) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            payment_type|zone
+            cat|str
+            ---
+            Credit card|Midtown East
+            Credit card|Upper West Side
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/categories.parquet") |
+            filter(!contains(payment_type, "^C"), contains(zone, "Airport")) |
+            select(payment_type, zone) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 2)
+            payment_type|zone
+            cat|str
+            ---
+            Dispute|LaGuardia Airport
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/categories.parquet") |
+            filter(contains(tags, "^ca")) |
+            select(payment_type, tags) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            payment_type|tags
+            cat|list[cat]
+            ---
+            Credit card|["card", "tip"]
+            Cash|["cash"]
+            Credit card|["card"]
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/categories.parquet") |
+            filter(!contains(tags, "^ca")) |
+            select(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 1)
+            payment_type
+            cat
+            ---
+            Dispute
+            No charge
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_len() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            filter(len(tags) >= 4) |
+            mutate(tags_len = len(tags)) |
+            select(shape_id, tags_len) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            shape_id|tags_len
+            u32|u32
+            ---
+            1|4
+            7|4
+            10|4
+            ---
+       "#
+        )
+    );
+
+    // Length of string columns.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(len(payment_type) > 7) |
+            count(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            payment_type|n
+            str|u32
+            ---
+            Credit card|185
+            No charge|1
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | filter(len(total_amount) > 2)"#,
+        "in step 2 (`filter`) at line 1: filter error: `len` column 'total_amount' must be list or String"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_between() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+          select(ends_with("time")) |
+          filter(between(tpep_pickup_datetime, dt("2022-11-01 17:00:00"), dt("2022-11-02 02:00:00"))) |
+          arrange(tpep_pickup_datetime) |
+          show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            tpep_pickup_datetime|tpep_dropoff_datetime
+            datetime[ns]|datetime[ns]
+            ---
+            2022-11-01 17:43:51|2022-11-01 17:52:45
+            2022-11-01 17:48:38|2022-11-01 17:59:55
+            2022-11-01 19:25:41|2022-11-01 19:32:33
+            2022-11-01 19:39:09|2022-11-01 19:45:10
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+          filter(between(total_amount, 10, 10.5)) |
+          select(total_amount) |
+          arrange(total_amount) |
+          show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 1)
+            total_amount
+            f64
+            ---
+            10.3
+            10.3
+            10.38
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+          filter(between(tpep_pickup_datetime, "2022-11-01", dt("2022-11-02")))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: between bounds for column 'tpep_pickup_datetime' must be dt values"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+          filter(between(tpep_pickup_datetime, dt("2022-11-03"), dt("2022-11-02")))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: between lower bound 2022-11-03 00:00:00 is after upper bound 2022-11-02 00:00:00"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+          filter(between(total_amount, dt("2022-11-01"), dt("2022-11-02")))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: between column 'total_amount' must be a date or datetime, found f64"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_type_mismatch() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(contains(payment_type, 5))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: contains: column 'payment_type' is str but pattern is a number"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            filter(!is_null(ints) & !contains(ints, "3"))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: contains: column 'ints' is list[u32] but pattern is a string"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            filter(contains(tags, 1))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: contains: column 'tags' is list[str] but pattern is a number"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(total_amount > 10, between(payment_type, 1, 3))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: between: column 'payment_type' is str but lower bound is a number"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(between(total_amount, 10, "20"))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`filter`) at line 2: filter error: between: column 'total_amount' is f64 but upper bound is a string"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_bool_literal() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(big = total_amount > 70) |
+            filter(big == true, payment_type == "Credit card") |
+            select(total_amount, big) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            total_amount|big
+            f64|bool
+            ---
+            77.6|true
+            70.69|true
+            74.22|true
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(big = total_amount > 70) |
+            filter(false != big) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            8
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
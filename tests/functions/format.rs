@@ -0,0 +1,73 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use dply::interpreter::{self, OutputFormat};
+use indoc::indoc;
+
+#[test]
+fn format_ndjson() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            head(3)
+    "#};
+
+    let output = interpreter::eval_to_string_with_format(input, OutputFormat::Ndjson)?;
+    assert_eq!(
+        output,
+        indoc!(
+            r#"
+            {"VendorID":2,"payment_type":"Credit card","total_amount":22.56}
+            {"VendorID":2,"payment_type":"Cash","total_amount":9.8}
+            {"VendorID":2,"payment_type":"Credit card","total_amount":17.76}
+            "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn format_csv() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(payment_type) |
+            show()
+    "#};
+
+    let output = interpreter::eval_to_string_with_format(input, OutputFormat::Csv)?;
+    assert_eq!(
+        output,
+        indoc!(
+            r#"
+            payment_type,n
+            Cash,53
+            Credit card,185
+            Dispute,2
+            No charge,1
+            Unknown,9
+            "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn format_glimpse_json() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/amounts.csv") |
+            glimpse()
+    "#};
+
+    let output = interpreter::eval_to_string_with_format(input, OutputFormat::Json)?;
+    assert_eq!(
+        output.trim(),
+        concat!(
+            r#"[{"dtype":"i64","name":"id","sample":["1","2","3","4","5","6","7"]},"#,
+            r#"{"dtype":"str","name":"amount","sample":["12"," 7 ","n/a",null,"3.5","-4","1e3"]}]"#
+        )
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn glimpse_parquet() -> Result<()> {
@@ -82,3 +82,33 @@ fn glimpse_csv() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn glimpse_format_json() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/amounts.csv") | amounts
+        csv("tests/data/bands.csv") |
+            glimpse(amounts, format = "json") |
+            glimpse(format = "json")
+    "#};
+
+    assert_interpreter!(
+        input,
+        concat!(
+            r#"[{"dtype":"i64","name":"id","sample":["1","2","3","4","5","6","7"]},"#,
+            r#"{"dtype":"str","name":"amount","sample":["12"," 7 ","n/a",null,"3.5","-4","1e3"]}]"#,
+            "\n",
+            r#"[{"dtype":"str","name":"band","sample":["short","medium","long"]},"#,
+            r#"{"dtype":"i64","name":"low","sample":["0","1","2"]},"#,
+            r#"{"dtype":"i64","name":"high","sample":["1","2","1000"]}]"#,
+            "\n"
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"csv("tests/data/bands.csv") | glimpse(format = "csv")"#,
+        "in step 2 (`glimpse`) at line 1: glimpse error: unknown format 'csv', use json or table"
+    );
+
+    Ok(())
+}
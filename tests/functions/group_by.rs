@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn group_by_mean_sd_var() -> Result<()> {
@@ -285,3 +285,519 @@ fn summarize_list() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn group_by_unknown_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            group_by(vendor, payment_type, payment) |
+            summarize(n = n())
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`group_by`) at line 3: group_by error: Unknown columns: vendor, payment; \
+         available: VendorID, payment_type, total_amount"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_any_all() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(
+                has_negative = any(total_amount < 0),
+                all_positive = all(total_amount > 0),
+                no_tip_over_50 = any(total_amount > 50 & tip_amount == 0),
+                n = n()
+            ) |
+            arrange(desc(n)) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 5)
+            payment_type|has_negative|all_positive|no_tip_over_50|n
+            str|bool|bool|bool|u32
+            ---
+            Credit card|false|true|true|185
+            Cash|false|true|true|53
+            Unknown|false|true|false|9
+            Dispute|true|false|false|2
+            No charge|false|true|false|1
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            summarize(
+                has_negative = any(total_amount < 0),
+                all_positive = all(total_amount > 0)
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 2)
+            has_negative|all_positive
+            bool|bool
+            ---
+            true|false
+            ---
+       "#
+        )
+    );
+
+    // String comparisons in predicates.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(
+                has_dispute = any(payment_type == "Dispute"),
+                all_paid = all(total_amount > 0)
+            ) |
+            arrange(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 3)
+            payment_type|has_dispute|all_paid
+            str|bool|bool
+            ---
+            Cash|false|true
+            Credit card|false|true
+            Dispute|true|false
+            No charge|false|true
+            Unknown|false|true
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_join_count() -> Result<()> {
+    // Counts from summarize and count have the same type and can be joined.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(payment_type) |
+            counts
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(n = n()) |
+            inner_join(counts) |
+            arrange(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 2)
+            payment_type|n
+            str|u32
+            ---
+            Cash|53
+            Credit card|185
+            Dispute|2
+            No charge|1
+            Unknown|9
+            ---
+       "#
+        )
+    );
+
+    // Null values are counted as rows.
+    let input = indoc! {r#"
+        csv("tests/data/amounts.csv") |
+            select(amount) |
+            summarize(n = n()) |
+            show()
+        csv("tests/data/amounts.csv") |
+            select(amount) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            7
+            ---
+            shape: (1, 1)
+            n
+            u32
+            ---
+            7
+            ---
+       "#
+        )
+    );
+
+    // The alias is kept when `n()` runs directly on a reader.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            summarize(rows = n()) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            rows
+            u32
+            ---
+            250
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") |
+            summarize(rows = n()) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            rows
+            u32
+            ---
+            265
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_rollup() -> Result<()> {
+    // Summarize counts per payment type and passenger count, then roll them
+    // up per payment type keeping the integer type of the counts.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type, passenger_count) |
+            summarize(n = n()) |
+            group_by(payment_type) |
+            summarize(total = sum(n), groups = n(), largest = max(n)) |
+            arrange(desc(total)) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 4)
+            payment_type|total|groups|largest
+            str|u32|u32|u32
+            ---
+            Credit card|185|6|144
+            Cash|53|6|36
+            Unknown|9|1|9
+            Dispute|2|2|1
+            No charge|1|1|1
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_keep() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") |
+            rename(PULocationID = LocationID) |
+            zones
+        parquet("tests/data/nyctaxi.parquet") |
+            inner_join(zones) |
+            group_by(PULocationID) |
+            summarize(n = n(), borough = keep(Borough), zone = keep(Zone)) |
+            arrange(desc(n), PULocationID) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            PULocationID|n|borough|zone
+            i64|u32|str|str
+            ---
+            237|17|Manhattan|Upper East Side South
+            161|12|Manhattan|Midtown Center
+            163|12|Manhattan|Midtown North
+            132|10|Queens|JFK Airport
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") |
+            group_by(Borough) |
+            summarize(n = n(), service_zone = keep(service_zone)) |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 4 (`show`) at line 4: `keep` column 'service_zone' has more than one value in a group"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_summarize_arith() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(tip_ratio = sum(tip_amount) / sum(total_amount), n = n()) |
+            arrange(desc(n)) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 3)
+            payment_type|tip_ratio|n
+            str|f64|u32
+            ---
+            Credit card|0.155048|185
+            Cash|0.0|53
+            Unknown|0.114804|9
+            Dispute|-0.0|2
+            No charge|0.0|1
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            summarize(range = max(total_amount) - min(total_amount), n = n() + 1) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 2)
+            range|n
+            f64|f64
+            ---
+            92.66|251.0
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            summarize(n = n() % 7, m = max(passenger_count) % 2.5) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 2)
+            n|m
+            u32|f64
+            ---
+            5|1.0
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | summarize(n = n() % 0)"#,
+        "in step 2 (`summarize`) at line 1: summarize error: `%` divisor cannot be zero"
+    );
+
+    // Division of integer aggregates is a float division.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            summarize(a = sum(passenger_count) / n()) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            a
+            f64
+            ---
+            1.424
+            ---
+       "#
+        )
+    );
+
+    // Only numeric aggregates can be arithmetic operands.
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | summarize(r = list(passenger_count) + 1)"#,
+        "in step 2 (`summarize`) at line 1: Invalid argument 'list(passenger_count) + 1' for function 'summarize'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_multi_level() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(VendorID, payment_type) |
+            summarize(n = n()) |
+            group_by(VendorID) |
+            summarize(mean_n = mean(n), payment_types = n()) |
+            arrange(VendorID) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 3)
+            VendorID|mean_n|payment_types
+            i64|f64|u32
+            ---
+            1|13.75|4
+            2|48.75|4
+            ---
+       "#
+        )
+    );
+
+    // Checked before reading the file.
+    let input = indoc! {r#"
+        parquet("tests/data/missing.parquet") |
+            group_by(VendorID) |
+            group_by(payment_type) |
+            summarize(n = n())
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`group_by`) at line 3: group_by error: must call summarize after a group_by"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn group_by_selectors() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(starts_with("payment"), contains("rate"), payment_type) |
+            summarize(n = n()) |
+            arrange(desc(n)) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            payment_type|rate_code|n
+            str|str|u32
+            ---
+            Credit card|Standard|175
+            Cash|Standard|50
+            Unknown|null|9
+            Credit card|JFK|8
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(starts_with("category_")) |
+            summarize(n = n())
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`group_by`) at line 2: group_by error: starts_with(\"category_\") doesn't match any column"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn summarize_ungrouped() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(VendorID == 1) |
+            summarize(
+                total = sum(total_amount),
+                n = n(),
+                median = median(total_amount),
+                has_negative = any(total_amount < 0),
+                vendor = keep(VendorID)
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 5)
+            total|n|median|has_negative|vendor
+            f64|u32|f64|bool|i64
+            ---
+            1253.39|55|18.17|false|1
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn head() -> Result<()> {
@@ -77,3 +77,60 @@ fn head_with_limit() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn head_variable() -> Result<()> {
+    // Peeking at a variable doesn't change the active dataframe.
+    let input = indoc! {r#"
+        csv("tests/data/bands.csv") | bands
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, trip_distance) |
+            head(bands, 2) |
+            show(bands) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 3)
+            band|low|high
+            str|i64|i64
+            ---
+            short|0|1
+            medium|1|2
+            ---
+            shape: (3, 3)
+            band|low|high
+            str|i64|i64
+            ---
+            short|0|1
+            medium|1|2
+            long|2|1000
+            ---
+            shape: (3, 2)
+            payment_type|trip_distance
+            str|f64
+            ---
+            Credit card|3.14
+            Cash|1.06
+            Credit card|2.36
+            ---
+      "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            head(zones)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`head`) at line 3: head error: undefined variable 'zones'"
+    );
+
+    Ok(())
+}
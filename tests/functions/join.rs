@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn left_join() -> Result<()> {
@@ -174,6 +174,63 @@ fn outer_join() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn outer_join_coalesce_keys() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            select(id = shape_id) |
+            mutate(right_val = id * 2) |
+            filter(id > 4) |
+            right_df
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            filter(shape_id < 8) |
+            mutate(left_val = shape_id * 2) |
+            left_df
+
+        left_df |
+            outer_join(right_df, shape_id == id, coalesce_keys = true) |
+            arrange(shape_id) |
+            filter(shape_id > 5 & shape_id < 10) |
+            show()
+
+        left_df |
+            outer_join(right_df, shape_id == id, coalesce_keys = false) |
+            arrange(shape_id, id) |
+            filter(id > 5 & id < 10) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            shape_id|left_val|right_val
+            u32|f64|f64
+            ---
+            6|12.0|12.0
+            7|14.0|14.0
+            8|null|16.0
+            9|null|18.0
+            ---
+            shape: (4, 4)
+            shape_id|left_val|id|right_val
+            u32|f64|u32|f64
+            ---
+            6|12.0|6|12.0
+            7|14.0|7|14.0
+            null|null|8|16.0
+            null|null|9|18.0
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
 #[test]
 fn cross_join() -> Result<()> {
     let input = indoc! {r#"
@@ -308,3 +365,220 @@ fn anti_join() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn anti_filter() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            select(id = shape_id) |
+            filter(id > 2) |
+            excluded_df
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            filter(shape_id < 8) |
+            anti_filter(excluded_df, shape_id == id) |
+            arrange(shape_id) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 1)
+            shape_id
+            u32
+            ---
+            1
+            2
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | anti_filter(excluded_df)"#,
+        "in step 2 (`anti_filter`) at line 1: anti_filter error: undefined dataframe variable 'excluded_df'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cross_join_max_output_rows() -> Result<()> {
+    let input = indoc! {r#"
+        config(max_output_rows = 1000)
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            right_df
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            cross_join(right_df) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            shape_id|shape_id_right
+            u32|u32
+            ---
+            1|1
+            1|2
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(max_output_rows = 1000)
+
+            parquet("tests/data/lists.parquet") |
+                select(shape_id) |
+                right_df
+
+            parquet("tests/data/lists.parquet") |
+                select(shape_id) |
+                cross_join(right_df) |
+                show()
+        "#},
+        "in step 4 (`show`) at line 10: show error: output has more than 1000 rows, see config max_output_rows"
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(max_output_rows = 1000)
+
+            parquet("tests/data/lists.parquet") |
+                select(shape_id) |
+                right_df
+
+            parquet("tests/data/lists.parquet") |
+                select(shape_id) |
+                cross_join(right_df) |
+                csv("/tmp/dply_cross_join.csv", overwrite = true)
+        "#},
+        "in step 4 (`csv`) at line 10: csv error: output has more than 1000 rows, see config max_output_rows"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn inner_join_inequality() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/bands.csv") | bands
+
+        parquet("tests/data/nyctaxi.parquet") |
+            select(trip_distance) |
+            inner_join(bands, trip_distance >= low, trip_distance < high) |
+            count(band, sort = true) |
+            show()
+
+        # Boundary values are in the band where they are the lower bound.
+        parquet("tests/data/nyctaxi.parquet") |
+            select(trip_distance) |
+            inner_join(bands, trip_distance >= low, trip_distance < high) |
+            filter(trip_distance == 0 | trip_distance == 1 | trip_distance == 2) |
+            distinct(trip_distance, band) |
+            arrange(trip_distance) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            band|n
+            str|u32
+            ---
+            long|115
+            medium|91
+            short|44
+            ---
+            shape: (3, 2)
+            trip_distance|band
+            f64|str
+            ---
+            0.0|short
+            1.0|medium
+            2.0|long
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            csv("tests/data/bands.csv") | bands
+            parquet("tests/data/nyctaxi.parquet") | inner_join(bands, trip_distance >= lo)
+        "#},
+        "in step 2 (`inner_join`) at line 2: join error: Unknown column 'lo'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn inner_join_inequality_with_keys() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            filter(shape_id < 4) |
+            right_df
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            filter(shape_id < 4) |
+            mutate(other_id = shape_id + 1) |
+            inner_join(right_df, shape_id == shape_id, other_id > shape_id) |
+            arrange(shape_id) |
+            show()
+
+        parquet("tests/data/lists.parquet") |
+            select(shape_id) |
+            filter(shape_id < 4) |
+            mutate(other_id = shape_id + 1) |
+            inner_join(
+                right_df,
+                shape_id == shape_id,
+                other_id > shape_id,
+                coalesce_keys = false
+            ) |
+            arrange(shape_id) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            shape_id|other_id
+            u32|f64
+            ---
+            1|2.0
+            2|3.0
+            3|4.0
+            ---
+            shape: (3, 3)
+            shape_id|other_id|shape_id_right
+            u32|f64|u32
+            ---
+            1|2.0|1
+            2|3.0|2
+            3|4.0|3
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
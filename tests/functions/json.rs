@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn json_load() -> Result<()> {
@@ -61,3 +61,86 @@ fn json_field() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn json_path() -> Result<()> {
+    let input = indoc! {r#"
+        json("tests/data/envelope.json", path = "data.rows") |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 3)
+            id|name|score
+            i64|str|f64
+            ---
+            1|alpha|9.5
+            2|beta|7.25
+            3|gamma|null
+            ---
+        "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"json("tests/data/envelope.json", path = "data.total") | show()"#,
+        "in step 1 (`json`) at line 1: json error: path 'data.total' in 'tests/data/envelope.json' is not an array of objects"
+    );
+
+    assert_interpreter_error!(
+        r#"json("tests/data/envelope.json", path = "data.items") | show()"#,
+        "in step 1 (`json`) at line 1: json error: path 'data.items' not found in 'tests/data/envelope.json'"
+    );
+
+    assert_interpreter_error!(
+        r#"json("tests/data/envelope.json", path = "data.rows", schema_rows = 10) | show()"#,
+        "in step 1 (`json`) at line 1: json error: `schema_rows` cannot be used with `path`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn json_schema_rows() -> Result<()> {
+    let input = indoc! {r#"
+        json("tests/data/github.json", schema_rows = 1) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            4
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn json_write_read_options() -> Result<()> {
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | json("tests/data/never.json", path = "data.rows")"#,
+        "in step 2 (`json`) at line 1: json error: `path` and `schema_rows` can only be used when reading"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | json("tests/data/never.json", schema_rows = 10)"#,
+        "in step 2 (`json`) at line 1: json error: `path` and `schema_rows` can only be used when reading"
+    );
+
+    assert!(!std::path::Path::new("tests/data/never.json").exists());
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::assert_interpreter;
+
+#[test]
+fn lineage() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/zones.csv") | select(LocationID, Zone) | zones
+        parquet("tests/data/nyctaxi.parquet") |
+            select(PULocationID, DOLocationID, trip_distance) |
+            rename(distance = trip_distance) |
+            mutate(km = distance * 1.609) |
+            inner_join(zones, PULocationID == LocationID) |
+            inner_join(zones, DOLocationID == LocationID) |
+            lineage()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 2)
+            column|provenance
+            str|str
+            ---
+            PULocationID|parquet("tests/data/nyctaxi.parquet")
+            DOLocationID|parquet("tests/data/nyctaxi.parquet")
+            distance|trip_distance <- parquet("tests/data/nyctaxi.parquet")
+            km|distance * 1.609
+            Zone|zones.Zone <- csv("tests/data/zones.csv")
+            Zone_right|zones.Zone <- csv("tests/data/zones.csv")
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn lineage_summarize() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(mean_price = mean(total_amount), n = n()) |
+            lineage()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            column|provenance
+            str|str
+            ---
+            payment_type|parquet("tests/data/nyctaxi.parquet")
+            mean_price|mean(total_amount)
+            n|n()
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
@@ -3,20 +3,36 @@
 
 //! Test binary for all dply functions.
 mod arrange;
+mod assert;
+mod check;
 mod count;
+mod count_rows;
+mod csv;
 mod df_var;
 mod distinct;
+mod error;
+mod excel;
+mod expect_schema;
 mod filter;
+mod format;
 mod glimpse;
 mod group_by;
 mod head;
 mod join;
 mod json;
+mod lineage;
 mod mutate;
+mod parquet;
+mod pivot_count;
 mod relocate;
 mod rename;
+mod reorder_like;
+mod sample;
 mod select;
 mod show;
+mod sqlite;
+mod struct_fields;
+mod top_n;
 mod unnest;
 
 macro_rules! assert_interpreter {
@@ -35,4 +51,22 @@ macro_rules! assert_interpreter {
     };
 }
 
+macro_rules! assert_interpreter_error {
+    ($input:expr, $expected:expr) => {
+        match dply::interpreter::eval_to_string($input) {
+            Ok(output) => panic!("Expected error for:\n{}\nfound:\n\n{output}", $input),
+            Err(e) => {
+                let error = e.to_string();
+                if error != $expected {
+                    panic!(
+                        "Interpreter error:\nexpected:\n\n{}\nfound:\n\n{}",
+                        $expected, error
+                    );
+                }
+            }
+        }
+    };
+}
+
 pub(crate) use assert_interpreter;
+pub(crate) use assert_interpreter_error;
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn mutate_arith() -> Result<()> {
@@ -58,7 +58,7 @@ fn mutate_arith() -> Result<()> {
             r#"
             shape: (15, 1)
             group_id
-            u64
+            u32
             ---
             1
             2
@@ -251,152 +251,238 @@ fn mutate_dt() -> Result<()> {
 }
 
 #[test]
-fn mutate_len() -> Result<()> {
+fn mutate_strict() -> Result<()> {
+    // Invalid dates are an error by default.
+    let input = indoc! {r#"
+        csv("tests/data/dates.csv") |
+            mutate(ts = ymd_hms(date)) |
+            show()
+    "#};
+
+    let err = dply::interpreter::eval_to_string(input).unwrap_err();
+    assert!(
+        err.to_string().starts_with(
+            "in step 3 (`show`) at line 3: invalid operation: conversion from `str` to `datetime[ns]` failed"
+        ),
+        "{err}"
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(strict = true)
+            csv("tests/data/dates.csv") |
+                mutate(ts = ymd_hms(date)) |
+                show()
+        "#},
+        "in step 3 (`show`) at line 4: `ymd_hms` cannot convert value \"not a date\" in row 2"
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(strict = true)
+            csv("tests/data/amounts.csv") |
+                mutate(amount = parse_float(amount)) |
+                show()
+        "#},
+        "in step 3 (`show`) at line 4: `parse_float` cannot convert value \"n/a\" in row 3"
+    );
+
+    // Rows are counted in the dataframe of the failed step.
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(strict = true)
+            csv("tests/data/amounts.csv") |
+                filter(id > 1) |
+                mutate(amount = parse_int(amount)) |
+                show()
+        "#},
+        "in step 4 (`show`) at line 5: `parse_int` cannot convert value \"n/a\" in row 2"
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            config(strict = true)
+            parquet("tests/data/nyctaxi.parquet") |
+                mutate(secs = total_amount * 1e12) |
+                mutate(ts = from_epoch(secs)) |
+                show()
+        "#},
+        "in step 4 (`show`) at line 5: `from_epoch` cannot convert value 2.2560e13 in row 1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_list_aggregates() -> Result<()> {
     let input = indoc! {r#"
         parquet("tests/data/lists.parquet") |
             mutate(
-                ints_len = len(ints),
-                floats_len = len(floats),
-                tags_len = len(tags)
+                ints_max = list_max(ints),
+                floats_min = list_min(floats),
+                ints_sum = list_sum(ints),
+                floats_mean = list_mean(floats),
+                tags_max = list_max(tags)
             ) |
-            select(ints_len, floats_len, tags_len) |
-            head()
+            select(ints, floats, ints_max, floats_min, ints_sum, floats_mean, tags_max) |
+            head(4)
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (10, 3)
-            ints_len|floats_len|tags_len
-            u32|u32|u32
+            shape: (4, 7)
+            ints|floats|ints_max|floats_min|ints_sum|floats_mean|tags_max
+            list[u32]|list[f64]|u32|f64|u32|f64|str
             ---
-            3|4|4
-            1|3|1
-            0|4|1
-            2|4|1
-            0|4|3
-            1|1|3
-            4|1|4
-            0|2|0
-            4|0|0
-            1|4|4
+            [3, 88, 94]|[2.5, 3.5, 6.0, 23.0]|94|2.5|185|8.75|tag8
+            [73]|[3.5, 15.0, 23.0]|73|3.5|73|13.833333|tag9
+            null|[1.0, 2.5, 6.0, 6.0]|null|1.0|null|3.875|tag5
+            [43, 97]|[2.5, 2.5, 3.5, 19.0]|97|2.5|140|6.875|tag7
             ---
        "#
         )
     );
 
-    // Lengths on strings
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_sum(shape_id))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_sum` column 'shape_id' must be a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_list_distinct() -> Result<()> {
     let input = indoc! {r#"
-        parquet("tests/data/nyctaxi.parquet") |
-            count(rate_code) |
-            mutate(rate_len = len(rate_code)) |
-            arrange(rate_code) |
-            head()
+        parquet("tests/data/lists.parquet") |
+            mutate(
+                unique_tags = list_distinct(tags),
+                tags_len = len(tags),
+                unique_len = len(unique_tags)
+            ) |
+            filter(tags_len > unique_len) |
+            select(tags, unique_tags) |
+            head(3)
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (4, 3)
-            rate_code|n|rate_len
-            str|u32|u32
+            shape: (3, 2)
+            tags|unique_tags
+            list[str]|list[str]
             ---
-            JFK|11|3
-            Negotiated|2|10
-            Standard|228|8
-            null|9|null
+            ["tag2", "tag5", "tag8", "tag8"]|["tag2", "tag5", "tag8"]
+            ["tag5", "tag6", "tag7", "tag7"]|["tag5", "tag6", "tag7"]
+            ["tag1", "tag5", "tag9", "tag9"]|["tag1", "tag5", "tag9"]
             ---
        "#
         )
     );
 
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_distinct(shape_id))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_distinct` column 'shape_id' must be a list"
+    );
+
     Ok(())
 }
 
 #[test]
-fn mutate_row_number() -> Result<()> {
-    // When using the row() function we need to select another column otherwise we
-    // get error from the planner.
+fn mutate_list_concat() -> Result<()> {
     let input = indoc! {r#"
-        parquet("tests/data/nyctaxi.parquet") |
-            mutate(row = row() % 5) |
-            select(row, rate_code) |
-            head()
+        parquet("tests/data/lists.parquet") |
+            filter(shape_id < 6) |
+            mutate(
+                head_ints = list_slice(ints, 0, 1),
+                all_ints = list_concat(ints, head_ints)
+            ) |
+            select(ints, head_ints, all_ints) |
+            show()
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (10, 2)
-            row|rate_code
-            u64|str
+            shape: (5, 3)
+            ints|head_ints|all_ints
+            list[u32]|list[u32]|list[u32]
             ---
-            1|Standard
-            2|Standard
-            3|Standard
-            4|Standard
-            0|Standard
-            1|Standard
-            2|Standard
-            3|Standard
-            4|Standard
-            0|Standard
+            [3, 88, 94]|[3]|[3, 88, 94, 3]
+            [73]|[73]|[73, 73]
+            null|null|null
+            [43, 97]|[43]|[43, 97, 43]
+            null|null|null
             ---
        "#
         )
     );
 
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_concat(ints, shape_id))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_concat` column 'shape_id' must be a list"
+    );
+
     Ok(())
 }
 
 #[test]
-fn mutate_field() -> Result<()> {
-    // Extract a field from a struct.
+fn mutate_list_get() -> Result<()> {
     let input = indoc! {r#"
-        parquet("tests/data/structs.parquet") |
-            filter(!is_null(points)) |
-            unnest(points) |
+        parquet("tests/data/lists.parquet") |
+            filter(shape_id < 9) |
             mutate(
-                x = field(points, x),
-                y = field(points, y)
+                first_tag = list_get(tags, 0),
+                last_int = list_get(ints, -1),
+                third_int = list_get(ints, 2)
             ) |
-            select(shape_id, x, y) |
-            head()
+            select(ints, first_tag, last_int, third_int) |
+            show()
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (10, 3)
-            shape_id|x|y
-            u32|f32|f32
+            shape: (8, 4)
+            ints|first_tag|last_int|third_int
+            list[u32]|str|u32|u32
             ---
-            1|-7.144482|-2.752852
-            1|-3.377404|-2.862458
-            1|-4.05302|6.336014
-            3|-8.744724|-0.039072
-            4|-0.807573|-7.81899
-            5|-2.831063|5.288568
-            6|4.039896|-3.030655
-            7|4.160488|9.694407
-            7|-7.926216|-4.505739
-            7|8.11179|8.441616
+            [3, 88, 94]|tag2|94|94
+            [73]|tag9|73|null
+            null|tag5|null|null
+            [43, 97]|tag7|97|null
+            null|tag2|null|null
+            [65]|tag2|65|null
+            [1, 22, 61, 87]|tag5|87|61
+            null|null|null|null
             ---
        "#
         )
     );
 
-    // Lengths on strings
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_get(shape_id, 0))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_get` column 'shape_id' must be a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_list_join() -> Result<()> {
     let input = indoc! {r#"
-        parquet("tests/data/nyctaxi.parquet") |
-            count(rate_code) |
-            mutate(rate_len = len(rate_code)) |
-            arrange(rate_code) |
-            head()
+        parquet("tests/data/lists.parquet") |
+            mutate(
+                ints_str = list_join(ints, "-"),
+                tags_str = list_join(tags, ", ")
+            ) |
+            select(ints, ints_str, tags_str) |
+            head(4)
     "#};
 
     assert_interpreter!(
@@ -404,92 +490,1664 @@ fn mutate_field() -> Result<()> {
         indoc!(
             r#"
             shape: (4, 3)
-            rate_code|n|rate_len
-            str|u32|u32
+            ints|ints_str|tags_str
+            list[u32]|str|str
             ---
-            JFK|11|3
-            Negotiated|2|10
-            Standard|228|8
-            null|9|null
+            [3, 88, 94]|3-88-94|tag2, tag5, tag8, tag8
+            [73]|73|tag9
+            null|null|tag5
+            [43, 97]|43-97|tag7
             ---
        "#
         )
     );
 
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_join(shape_id, ","))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_join` column 'shape_id' must be a list"
+    );
+
     Ok(())
 }
 
 #[test]
-fn mutate_durations() -> Result<()> {
-    // Convert from duration to integer
+fn mutate_list_slice() -> Result<()> {
     let input = indoc! {r#"
-        parquet("tests/data/nyctaxi.parquet") |
-            mutate(travel_time = tpep_dropoff_datetime - tpep_pickup_datetime) |
-            select(travel_time) |
+        parquet("tests/data/lists.parquet") |
             mutate(
-                travel_time_secs = secs(travel_time),
-                travel_time_millis = millis(travel_time),
-                travel_time_micros = micros(travel_time),
-                travel_time_nanos = nanos(travel_time)
-            )|
-            head(5)
+                first_two = list_slice(tags, 0, 2),
+                last = list_slice(ints, -1, 1)
+            ) |
+            select(ints, last, tags, first_two) |
+            head(4)
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (5, 5)
-            travel_time|travel_time_secs|travel_time_millis|travel_time_micros|travel_time_nanos
-            duration[ns]|i64|i64|i64|i64
+            shape: (4, 4)
+            ints|last|tags|first_two
+            list[u32]|list[u32]|list[str]|list[str]
             ---
-            18m 52s|1132|1132000|1132000000|1132000000000
-            6m 40s|400|400000|400000000|400000000000
-            13m 54s|834|834000|834000000|834000000000
-            15m 8s|908|908000|908000000|908000000000
-            20m 7s|1207|1207000|1207000000|1207000000000
+            [3, 88, 94]|[94]|["tag2", "tag5", "tag8", "tag8"]|["tag2", "tag5"]
+            [73]|[73]|["tag9"]|["tag9"]
+            null|null|["tag5"]|["tag5"]
+            [43, 97]|[97]|["tag7"]|["tag7"]
             ---
        "#
         )
     );
 
-    // Convert from integer to duration
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_slice(tags, 0, -1))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_slice` length must not be negative, got -1"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_slice(shape_id, 0, 1))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_slice` column 'shape_id' must be a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_list_sort() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            mutate(
+                desc_ints = list_sort(ints, descending = true),
+                asc_ints = list_sort(desc_ints),
+                desc_tags = list_sort(tags, descending = true)
+            ) |
+            select(ints, desc_ints, asc_ints, desc_tags) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            ints|desc_ints|asc_ints|desc_tags
+            list[u32]|list[u32]|list[u32]|list[str]
+            ---
+            [3, 88, 94]|[94, 88, 3]|[3, 88, 94]|["tag8", "tag8", "tag5", "tag2"]
+            [73]|[73]|[73]|["tag9"]
+            null|null|null|["tag5"]
+            [43, 97]|[97, 43]|[43, 97]|["tag7"]
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/lists.parquet") | mutate(x = list_sort(shape_id))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `list_sort` column 'shape_id' must be a list"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_bool() -> Result<()> {
     let input = indoc! {r#"
         parquet("tests/data/nyctaxi.parquet") |
-            mutate(travel_time = tpep_dropoff_datetime - tpep_pickup_datetime) |
-            select(travel_time) |
             mutate(
-                travel_time_secs = secs(travel_time),
-                travel_time_millis = millis(travel_time),
-                travel_time_micros = micros(travel_time),
-                travel_time_nanos = nanos(travel_time)
-            )|
+                expensive = total_amount > 20,
+                group = total_amount > 20 & passenger_count >= 2,
+                checked = true
+            ) |
+            count(expensive, group, checked, sort = true) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            expensive|group|checked|n
+            bool|bool|bool|u32
+            ---
+            false|false|true|165
+            true|false|true|61
+            true|true|true|18
+            true|null|true|6
+            ---
+       "#
+        )
+    );
+
+    // Filter on a boolean column.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(expensive = total_amount > 20) |
+            filter(expensive & passenger_count > 3) |
+            select(total_amount, passenger_count, expensive) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 3)
+            total_amount|passenger_count|expensive
+            f64|i64|bool
+            ---
+            21.3|5|true
+            23.76|5|true
+            24.3|4|true
+            24.3|4|true
+            31.56|4|true
+            66.36|5|true
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | filter(payment_type)"#,
+        "in step 2 (`filter`) at line 1: filter error: column 'payment_type' must be a boolean, found str"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_rolling() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(PULocationID) |
+            arrange(PULocationID) |
             mutate(
-                dtravel_time_millis = dmillis(travel_time_millis),
-                dtravel_time_micros = dmicros(travel_time_micros),
-                dtravel_time_nanos = dnanos(travel_time_nanos)
-            )|
-            select(travel_time, starts_with("dtravel")) |
-            head(5)
+                mean_3 = rolling_mean(n, 3),
+                sum_3 = rolling_sum(n, 3, min_periods = 1),
+                min_2 = rolling_min(n, 2),
+                max_2 = rolling_max(n, 2)
+            ) |
+            head(6)
     "#};
 
     assert_interpreter!(
         input,
         indoc!(
             r#"
-            shape: (5, 4)
-            travel_time|dtravel_time_millis|dtravel_time_micros|dtravel_time_nanos
-            duration[ns]|duration[μs]|duration[μs]|duration[μs]
+            shape: (6, 6)
+            PULocationID|n|mean_3|sum_3|min_2|max_2
+            i64|u32|f64|u32|u32|u32
             ---
-            18m 52s|18m 52s|18m 52s|18m 52s
-            6m 40s|6m 40s|6m 40s|6m 40s
-            13m 54s|13m 54s|13m 54s|13m 54s
-            15m 8s|15m 8s|15m 8s|15m 8s
-            20m 7s|20m 7s|20m 7s|20m 7s
+            4|2|null|2|null|null
+            13|1|null|3|1|2
+            24|2|1.666667|5|1|2
+            43|3|2.0|6|2|3
+            48|7|4.0|12|3|7
+            50|2|4.0|12|2|7
+            ---
+       "#
+        )
+    );
+
+    // Windows are computed within groups.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(payment_type, passenger_count) |
+            filter(payment_type == "Cash" | payment_type == "Dispute") |
+            arrange(payment_type, passenger_count) |
+            group_by(payment_type) |
+            mutate(sum_2 = rolling_sum(n, 2), max_2 = rolling_max(n, 2) * 2) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (8, 5)
+            payment_type|passenger_count|n|sum_2|max_2
+            str|i64|u32|u32|f64
+            ---
+            Cash|1|36|null|null
+            Cash|2|7|43|72.0
+            Cash|3|4|11|14.0
+            Cash|4|3|7|8.0
+            Cash|5|2|5|6.0
+            Cash|6|1|3|4.0
+            Dispute|1|1|null|null
+            Dispute|2|1|2|2.0
             ---
        "#
         )
     );
 
+    // Other expressions are not computed within groups.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            mutate(largest = max(total_amount)) |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`mutate`) at line 3: mutate error: 'largest' must use one of fill_group, fill_null, \
+        ntile, rolling_max, rolling_mean, rolling_min, rolling_sum after a group_by"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(x = rolling_mean(total_amount, 0))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `rolling_mean` window size must be a positive integer"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_len() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/lists.parquet") |
+            mutate(
+                ints_len = len(ints),
+                floats_len = len(floats),
+                tags_len = len(tags)
+            ) |
+            select(ints_len, floats_len, tags_len) |
+            head()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (10, 3)
+            ints_len|floats_len|tags_len
+            u32|u32|u32
+            ---
+            3|4|4
+            1|3|1
+            0|4|1
+            2|4|1
+            0|4|3
+            1|1|3
+            4|1|4
+            0|2|0
+            4|0|0
+            1|4|4
+            ---
+       "#
+        )
+    );
+
+    // Lengths on strings
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(rate_code) |
+            mutate(rate_len = len(rate_code)) |
+            arrange(rate_code) |
+            head()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            rate_code|n|rate_len
+            str|u32|u32
+            ---
+            JFK|11|3
+            Negotiated|2|10
+            Standard|228|8
+            null|9|null
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_mod() -> Result<()> {
+    // Float modulo if either operand is float, integer modulo otherwise, the
+    // result has the sign of the divisor.
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(
+                float_rhs = id % 2.5,
+                float_lhs = val_c % 2,
+                neg_lhs = (0 - val_c) % 2,
+                neg_rhs = id % -3
+            ) |
+            select(id, float_rhs, float_lhs, neg_lhs, neg_rhs) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 5)
+            id|float_rhs|float_lhs|neg_lhs|neg_rhs
+            i64|f64|f64|f64|i64
+            ---
+            1|1.0|null|null|-2
+            2|2.0|1.5|0.5|-1
+            3|0.5|0.5|1.5|0
+            4|1.5|null|null|-2
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"csv("tests/data/readings.csv") | mutate(x = id % 0)"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `%` divisor cannot be zero"
+    );
+
+    assert_interpreter_error!(
+        r#"csv("tests/data/readings.csv") | mutate(x = val_a % 0.0)"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `%` divisor cannot be zero"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_row_number() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(row = row() % 5) |
+            select(row, rate_code) |
+            head()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (10, 2)
+            row|rate_code
+            u64|str
+            ---
+            1|Standard
+            2|Standard
+            3|Standard
+            4|Standard
+            0|Standard
+            1|Standard
+            2|Standard
+            3|Standard
+            4|Standard
+            0|Standard
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(row = row()) |
+            select(row) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 1)
+            row
+            u64
+            ---
+            1
+            2
+            3
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(row = row()) |
+            filter(row > 248) |
+            select(row) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 1)
+            row
+            u64
+            ---
+            249
+            250
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_field() -> Result<()> {
+    // Extract a field from a struct.
+    let input = indoc! {r#"
+        parquet("tests/data/structs.parquet") |
+            filter(!is_null(points)) |
+            unnest(points) |
+            mutate(
+                x = field(points, x),
+                y = field(points, y)
+            ) |
+            select(shape_id, x, y) |
+            head()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (10, 3)
+            shape_id|x|y
+            u32|f32|f32
+            ---
+            1|-7.144482|-2.752852
+            1|-3.377404|-2.862458
+            1|-4.05302|6.336014
+            3|-8.744724|-0.039072
+            4|-0.807573|-7.81899
+            5|-2.831063|5.288568
+            6|4.039896|-3.030655
+            7|4.160488|9.694407
+            7|-7.926216|-4.505739
+            7|8.11179|8.441616
+            ---
+       "#
+        )
+    );
+
+    // Lengths on strings
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            count(rate_code) |
+            mutate(rate_len = len(rate_code)) |
+            arrange(rate_code) |
+            head()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            rate_code|n|rate_len
+            str|u32|u32
+            ---
+            JFK|11|3
+            Negotiated|2|10
+            Standard|228|8
+            null|9|null
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_durations() -> Result<()> {
+    // Convert from duration to integer
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(travel_time = tpep_dropoff_datetime - tpep_pickup_datetime) |
+            select(travel_time) |
+            mutate(
+                travel_time_secs = secs(travel_time),
+                travel_time_millis = millis(travel_time),
+                travel_time_micros = micros(travel_time),
+                travel_time_nanos = nanos(travel_time)
+            )|
+            head(5)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 5)
+            travel_time|travel_time_secs|travel_time_millis|travel_time_micros|travel_time_nanos
+            duration[ns]|i64|i64|i64|i64
+            ---
+            18m 52s|1132|1132000|1132000000|1132000000000
+            6m 40s|400|400000|400000000|400000000000
+            13m 54s|834|834000|834000000|834000000000
+            15m 8s|908|908000|908000000|908000000000
+            20m 7s|1207|1207000|1207000000|1207000000000
+            ---
+       "#
+        )
+    );
+
+    // Convert from integer to duration
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(travel_time = tpep_dropoff_datetime - tpep_pickup_datetime) |
+            select(travel_time) |
+            mutate(
+                travel_time_secs = secs(travel_time),
+                travel_time_millis = millis(travel_time),
+                travel_time_micros = micros(travel_time),
+                travel_time_nanos = nanos(travel_time)
+            )|
+            mutate(
+                dtravel_time_millis = dmillis(travel_time_millis),
+                dtravel_time_micros = dmicros(travel_time_micros),
+                dtravel_time_nanos = dnanos(travel_time_nanos)
+            )|
+            select(travel_time, starts_with("dtravel")) |
+            head(5)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 4)
+            travel_time|dtravel_time_millis|dtravel_time_micros|dtravel_time_nanos
+            duration[ns]|duration[μs]|duration[μs]|duration[μs]
+            ---
+            18m 52s|18m 52s|18m 52s|18m 52s
+            6m 40s|6m 40s|6m 40s|6m 40s
+            13m 54s|13m 54s|13m 54s|13m 54s
+            15m 8s|15m 8s|15m 8s|15m 8s
+            20m 7s|20m 7s|20m 7s|20m 7s
+            ---
+       "#
+        )
+    );
+
+    // Whole minutes, hours and days of a duration
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(travel_time = tpep_dropoff_datetime - tpep_pickup_datetime) |
+            select(travel_time) |
+            mutate(long_secs = secs(travel_time) * 150) |
+            mutate(long_time = dsecs(long_secs)) |
+            mutate(
+                travel_time_minutes = minutes(travel_time),
+                long_time_hours = hours(long_time),
+                long_time_days = days(long_time)
+            )|
+            select(travel_time, travel_time_minutes, long_time, long_time_hours, long_time_days) |
+            head(5)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 5)
+            travel_time|travel_time_minutes|long_time|long_time_hours|long_time_days
+            duration[ns]|i64|duration[μs]|i64|i64
+            ---
+            18m 52s|18|1d 23h 10m|47|1
+            6m 40s|6|16h 40m|16|0
+            13m 54s|13|1d 10h 45m|34|1
+            15m 8s|15|1d 13h 50m|37|1
+            20m 7s|20|2d 2h 17m 30s|50|2
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_epoch() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/timestamps.parquet") |
+            mutate(
+                unix_secs = epoch_seconds(nanos),
+                unix_millis = unix_secs + 0.5,
+                from_secs = from_epoch(unix_secs),
+                from_millis = from_epoch(unix_millis)
+            ) |
+            select(nanos, unix_secs, from_secs, from_millis) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 4)
+            nanos|unix_secs|from_secs|from_millis
+            datetime[ns]|i64|datetime[ns]|datetime[ns]
+            ---
+            2023-08-02 23:09:42.456642001|1691017782|2023-08-02 23:09:42|2023-08-02 23:09:42.500
+            2023-08-02 23:09:46.159043342|1691017786|2023-08-02 23:09:46|2023-08-02 23:09:46.500
+            2023-08-02 23:09:54.041828923|1691017794|2023-08-02 23:09:54|2023-08-02 23:09:54.500
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_timezone_convert() -> Result<()> {
+    // Daylight saving time ends on 2022-11-06 at 2:00 in New York, the ambiguous
+    // local times between 1:00 and 2:00 resolve to the earliest instant.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(tpep_pickup_datetime) |
+            filter(
+                tpep_pickup_datetime > dt("2022-11-06 00:00:00") &
+                tpep_pickup_datetime < dt("2022-11-06 12:00:00")
+            ) |
+            mutate(
+                ny_datetime = timezone_convert(tpep_pickup_datetime, "UTC", "America/New_York"),
+                utc_datetime = timezone_convert(tpep_pickup_datetime, "America/New_York", "UTC"),
+                rome_datetime = timezone_convert(ny_datetime, "America/New_York", "Europe/Rome")
+            ) |
+            arrange(tpep_pickup_datetime) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 4)
+            tpep_pickup_datetime|ny_datetime|utc_datetime|rome_datetime
+            datetime[ns]|datetime[ns, America/New_York]|datetime[ns, UTC]|datetime[ns, Europe/Rome]
+            ---
+            2022-11-06 00:27:16|2022-11-05 20:27:16 EDT|2022-11-06 04:27:16 UTC|2022-11-06 01:27:16 CET
+            2022-11-06 01:11:32|2022-11-05 21:11:32 EDT|2022-11-06 05:11:32 UTC|2022-11-06 02:11:32 CET
+            2022-11-06 02:53:59|2022-11-05 22:53:59 EDT|2022-11-06 07:53:59 UTC|2022-11-06 03:53:59 CET
+            2022-11-06 09:48:09|2022-11-06 04:48:09 EST|2022-11-06 14:48:09 UTC|2022-11-06 10:48:09 CET
+            2022-11-06 10:18:14|2022-11-06 05:18:14 EST|2022-11-06 15:18:14 UTC|2022-11-06 11:18:14 CET
+            2022-11-06 10:46:59|2022-11-06 05:46:59 EST|2022-11-06 15:46:59 UTC|2022-11-06 11:46:59 CET
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_set_tz() -> Result<()> {
+    // set_tz keeps the local time, convert_tz keeps the instant.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(tpep_pickup_datetime) |
+            mutate(
+                ny = set_tz(tpep_pickup_datetime, "America/New_York"),
+                rome = convert_tz(ny, "America/New_York", "Europe/Rome"),
+                utc = set_tz(rome, "UTC")
+            ) |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 4)
+            tpep_pickup_datetime|ny|rome|utc
+            datetime[ns]|datetime[ns, America/New_York]|datetime[ns, Europe/Rome]|datetime[ns, UTC]
+            ---
+            2022-11-22 19:27:01|2022-11-22 19:27:01 EST|2022-11-23 01:27:01 CET|2022-11-23 01:27:01 UTC
+            2022-11-27 16:43:26|2022-11-27 16:43:26 EST|2022-11-27 22:43:26 CET|2022-11-27 22:43:26 UTC
+            2022-11-12 16:58:37|2022-11-12 16:58:37 EST|2022-11-12 22:58:37 CET|2022-11-12 22:58:37 UTC
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(x = set_tz(total_amount, "UTC"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `set_tz` column 'total_amount' must be a datetime"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(x = convert_tz(tpep_pickup_datetime, "UTC", "Mars/Base"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `convert_tz` unknown time zone 'Mars/Base'"
+    );
+
+    assert_interpreter_error!(
+        indoc! {r#"
+            parquet("tests/data/nyctaxi.parquet") |
+                mutate(ny = set_tz(tpep_pickup_datetime, "America/New_York")) |
+                mutate(rome = timezone_convert(ny, "UTC", "Europe/Rome"))
+        "#},
+        "in step 3 (`mutate`) at line 3: mutate error: `timezone_convert` column 'ny' \
+        has time zone 'America/New_York', not 'UTC'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_ntile() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(quartile = ntile(total_amount, 4)) |
+            group_by(quartile) |
+            summarize(
+                n = n(),
+                min_amount = min(total_amount),
+                max_amount = max(total_amount)
+            ) |
+            arrange(quartile) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            quartile|n|min_amount|max_amount
+            i64|u32|f64|f64
+            ---
+            1|63|-8.3|12.36
+            2|62|12.36|15.99
+            3|63|16.3|22.88
+            4|62|23.16|84.36
+            ---
+       "#
+        )
+    );
+
+    // After a group_by buckets are computed within each group.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(payment_type == "Cash" | payment_type == "No charge") |
+            group_by(payment_type) |
+            mutate(half = ntile(total_amount, 2)) |
+            count(payment_type, half) |
+            arrange(payment_type, half) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 3)
+            payment_type|half|n
+            str|i64|u32
+            ---
+            Cash|1|27
+            Cash|2|26
+            No charge|1|1
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_parse_numbers() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/amounts.csv") |
+            mutate(
+                as_int = parse_int(amount),
+                as_float = parse_float(amount)
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (7, 4)
+            id|amount|as_int|as_float
+            i64|str|i64|f64
+            ---
+            1|12|12|12.0
+            2| 7 |7|7.0
+            3|n/a|null|null
+            4|null|null|null
+            5|3.5|null|3.5
+            6|-4|-4|-4.0
+            7|1e3|null|1000.0
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_cut() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(tier = cut(total_amount, [0, 10, 20, 50])) |
+            count(tier) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 2)
+            tier|n
+            cat|u32
+            ---
+            [-inf, 0)|1
+            [0, 10)|22
+            [10, 20)|142
+            [20, 50)|65
+            [50, inf)|20
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(tier = cut(total_amount, [10, 20], labels = ["low", "mid", "high"])) |
+            count(tier) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            tier|n
+            cat|u32
+            ---
+            low|23
+            mid|142
+            high|85
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_normalize() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(
+                norm = normalize(trip_distance),
+                scaled = normalize(trip_distance, -1, 1)
+            ) |
+            select(trip_distance, norm, scaled) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            trip_distance|norm|scaled
+            f64|f64|f64
+            ---
+            3.14|0.153922|-0.692157
+            1.06|0.051961|-0.896078
+            2.36|0.115686|-0.768627
+            5.2|0.254902|-0.490196
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(
+                norm = normalize(trip_distance),
+                scaled = normalize(trip_distance, -1, 1)
+            ) |
+            summarize(
+                min_norm = min(norm),
+                max_norm = max(norm),
+                min_scaled = min(scaled),
+                max_scaled = max(scaled)
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 4)
+            min_norm|max_norm|min_scaled|max_scaled
+            f64|f64|f64|f64
+            ---
+            0.0|1.0|-1.0|1.0
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_standardize() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(z = standardize(trip_distance)) |
+            select(trip_distance, z) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            trip_distance|z
+            f64|f64
+            ---
+            3.14|-0.077285
+            1.06|-0.585288
+            2.36|-0.267786
+            5.2|0.425834
+            ---
+       "#
+        )
+    );
+
+    // Mean is close to zero and standard deviation close to one.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(z = standardize(trip_distance)) |
+            summarize(mean_z = mean(z), sd_z = sd(z)) |
+            filter(
+                mean_z > -0.000001 & mean_z < 0.000001 &
+                sd_z > 0.999999 & sd_z < 1.000001
+            ) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            1
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_cut_bounds() -> Result<()> {
+    // Values equal to a break go in the bin starting at that break.
+    let input = indoc! {r#"
+        csv("tests/data/amounts.csv") |
+            mutate(value = parse_float(amount), bin = cut(value, [7, 12])) |
+            select(value, bin) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (7, 2)
+            value|bin
+            f64|cat
+            ---
+            12.0|[12, inf)
+            7.0|[7, 12)
+            null|null
+            null|null
+            3.5|[-inf, 7)
+            -4.0|[-inf, 7)
+            1000.0|[12, inf)
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(bin = cut(total_amount, 10, 20))"#,
+        "in step 2 (`mutate`) at line 1: Invalid argument 'cut(total_amount, 10, 20)' for function 'mutate'"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(bin = cut(total_amount, [10]))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `cut` needs at least two breaks"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(bin = cut(total_amount, [10, 20], labels = ["low", "high"]))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `cut` expected 3 labels for 2 breaks, got 2"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_bin() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(tier = bin(total_amount, 0, 10, 50, 100)) |
+            count(tier) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            tier|n
+            cat|u32
+            ---
+            null|1
+            [0, 10)|22
+            [10, 50)|207
+            [50, 100)|20
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_qcut() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(quartile = qcut(total_amount, 4)) |
+            count(quartile) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            quartile|n
+            cat|u32
+            ---
+            [-inf, 12.36)|59
+            [12.36, 16.145)|66
+            [16.145, 22.86)|62
+            [22.86, inf)|63
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(quartile = qcut(total_amount, 4, labels = ["Q1", "Q2", "Q3", "Q4"])) |
+            count(quartile) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            quartile|n
+            cat|u32
+            ---
+            Q1|59
+            Q2|66
+            Q3|62
+            Q4|63
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(q = qcut(total_amount, 4, labels = ["Q1"]))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `qcut` expected 4 labels, got 1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_str_pad() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(
+                id = str_pad(passenger_count, 5, "0"),
+                amount = format_num(total_amount, 2),
+                payment = str_pad(payment_type, 8, "*", "right"),
+                key = str_pad(passenger_count, 3, "0") + "-" + format_num(total_amount, 2)
+            ) |
+            select(id, amount, payment, key) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            id|amount|payment|key
+            str|str|str|str
+            ---
+            00001|22.56|Credit card|001-22.56
+            00002|9.80|Cash****|002-9.80
+            00001|17.76|Credit card|001-17.76
+            00001|26.16|Credit card|001-26.16
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(id = str_pad(payment_type, 5, "00"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `str_pad` fill must be a single character, got '00'"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(id = str_pad(payment_type, 5, "0", "up"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `str_pad` side must be 'left' or 'right', got 'up'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_hash() -> Result<()> {
+    // Equal values have equal hashes.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(h = hash(payment_type)) |
+            distinct(payment_type, h) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            5
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            distinct(payment_type) |
+            arrange(payment_type) |
+            mutate(
+                xxh64 = hash(payment_type, algorithm = "xxh64"),
+                fnv = hash(payment_type, algorithm = "fnv")
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 3)
+            payment_type|xxh64|fnv
+            str|u64|u64
+            ---
+            Cash|6647932650634400113|16135093018505504818
+            Credit card|417051003221448510|2255440796455612802
+            Dispute|17844084126624850005|4037160790934069179
+            No charge|3304435022326174095|15579477537492237806
+            Unknown|12635063240907552217|14236494692615132857
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            distinct(payment_type) |
+            arrange(payment_type) |
+            mutate(
+                xxh64 = hash(payment_type, algorithm = "xxh64", seed = 42),
+                fnv = hash(payment_type, seed = 42, algorithm = "fnv")
+            ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 3)
+            payment_type|xxh64|fnv
+            str|u64|u64
+            ---
+            Cash|13418904037143210317|14581209483876986660
+            Credit card|12189776430809114297|9613659414300826368
+            Dispute|1694491743117971286|18172076423175199429
+            No charge|10869316526408493674|9943341723350785304
+            Unknown|17053569456356651522|8658835502585499575
+            ---
+       "#
+        )
+    );
+
+    // A seed changes all hashes.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(h = hash(PULocationID), seeded = hash(PULocationID, seed = 7)) |
+            filter(h == seeded) |
+            count() |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            0
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(h = hash(payment_type, algorithm = "md5"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `hash` unknown algorithm 'md5', expected 'xxh64' or 'fnv'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_mixed_types() -> Result<()> {
+    // n is u32, avg is f64 and riders is i64.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            summarize(n = n(), avg = mean(total_amount), riders = max(passenger_count)) |
+            filter(payment_type != "Dispute") |
+            mutate(total = n * avg, rem = n % avg, diff = n - riders) |
+            select(payment_type, total, rem, diff) |
+            arrange(payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            payment_type|total|rem|diff
+            str|f64|f64|i64
+            ---
+            Cash|978.3|16.083019|47
+            Credit card|4140.07|5.969946|180
+            No charge|8.8|1.0|0
+            Unknown|241.63|9.0|null
+            ---
+       "#
+        )
+    );
+
+    // Mixed signed and u64 operands use f64 so that large values don't overflow.
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(row = row(), total = row + id, rem = row % 3) |
+            select(id, row, total, rem) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            id|row|total|rem
+            i64|u64|f64|u64
+            ---
+            1|1|2.0|1
+            2|2|4.0|2
+            3|3|6.0|0
+            4|4|8.0|1
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(x = payment_type + total_amount)"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `+` cannot combine str and f64 values, convert them with parse_int, parse_float, or format_num first"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_str_c() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(
+                label = str_c(payment_type, " / ", rate_code),
+                key = str_c(payment_type, rate_code, passenger_count, sep = "-")
+            ) |
+            select(label, key) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            label|key
+            str|str
+            ---
+            Credit card / Standard|Credit card-Standard-1
+            Cash / Standard|Cash-Standard-2
+            ---
+       "#
+        )
+    );
+
+    // Nulls propagate unless ignore_null is true.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            filter(is_null(rate_code)) |
+            mutate(
+                label = str_c(payment_type, " / ", rate_code),
+                key = str_c(payment_type, rate_code, sep = "-", ignore_null = true)
+            ) |
+            select(label, key) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            label|key
+            str|str
+            ---
+            null|Unknown
+            null|Unknown
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_format_string() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(label = format_string("{payment_type}: ${total_amount} ({{n}} = {passenger_count})")) |
+            select(label) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 1)
+            label
+            str
+            ---
+            Credit card: $22.56 ({n} = 1)
+            Cash: $9.8 ({n} = 2)
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(label = format_string("{payment_type"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: `format_string` missing closing brace in '{payment_type'"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | mutate(label = format_string("{payment}"))"#,
+        "in step 2 (`mutate`) at line 1: mutate error: Unknown column 'payment'; did you mean 'payment_type'?"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_concat_str() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(
+                key = concat_str(payment_type, rate_code, passenger_count, separator = "-"),
+                joined = concat_str(payment_type, rate_code)
+            ) |
+            select(key, joined) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 2)
+            key|joined
+            str|str
+            ---
+            Credit card-Standard-1|Credit cardStandard
+            Cash-Standard-2|CashStandard
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_coalesce() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(
+                first = coalesce(starts_with("val_")),
+                last = coalesce(val_c, val_b, val_a, 0)
+            ) |
+            select(id, first, last) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            id|first|last
+            i64|f64|f64
+            ---
+            1|1.5|1.5
+            2|2.5|3.5
+            3|4.5|4.5
+            4|null|0.0
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(value = coalesce(starts_with("x_"), val_a))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`mutate`) at line 2: mutate error: `coalesce` selector starts_with(\"x_\") doesn't match any column"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_fill_null() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(
+                mean = fill_null(val_c, strategy = "mean"),
+                median = fill_null(val_c, strategy = "median"),
+                min = fill_null(val_c, strategy = "min"),
+                max = fill_null(val_c, strategy = "max"),
+                zero = fill_null(val_c, strategy = "zero")
+            ) |
+            select(id, mean, median, min, max, zero) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 6)
+            id|mean|median|min|max|zero
+            i64|f64|f64|f64|f64|f64
+            ---
+            1|4.0|4.0|3.5|4.5|0.0
+            2|3.5|3.5|3.5|3.5|3.5
+            3|4.5|4.5|4.5|4.5|4.5
+            4|4.0|4.0|3.5|4.5|0.0
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(late = id > 2) |
+            group_by(late) |
+            mutate(val_c = fill_null(val_c, strategy = "mean")) |
+            select(id, late, val_c) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 3)
+            id|late|val_c
+            i64|bool|f64
+            ---
+            1|false|3.5
+            2|false|3.5
+            3|true|4.5
+            4|true|4.5
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/fruits.csv") |
+            mutate(name = fill_null(name, strategy = "mean"))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`mutate`) at line 2: mutate error: `fill_null` column 'name' must be numeric for strategy 'mean', found str"
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(val_c = fill_null(val_c, strategy = "mode"))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`mutate`) at line 2: mutate error: `fill_null` unknown strategy 'mode', \
+         expected 'mean', 'median', 'min', 'max' or 'zero'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn mutate_fill_group() -> Result<()> {
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(odd = id % 2) |
+            group_by(odd) |
+            mutate(val_a = fill_group(val_a), val_c = fill_group(val_c)) |
+            select(id, odd, val_a, val_c) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 4)
+            id|odd|val_a|val_c
+            i64|i64|f64|f64
+            ---
+            1|1|1.5|null
+            2|0|null|3.5
+            3|1|1.5|4.5
+            4|0|null|3.5
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        csv("tests/data/readings.csv") |
+            mutate(val_c = fill_group(val_c))
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`mutate`) at line 2: mutate error: `fill_group` must be called after a group_by"
+    );
+
     Ok(())
 }
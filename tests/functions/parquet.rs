@@ -0,0 +1,178 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::{formatdoc, indoc};
+use std::fs;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn parquet_partition_by() -> Result<()> {
+    let out_dir = std::env::temp_dir().join("dply_parquet_partition_by");
+    let _ = fs::remove_dir_all(&out_dir);
+
+    let input = formatdoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            parquet("{}", partition_by = VendorID, payment_type)
+    "#, out_dir.display()};
+    dply::interpreter::eval_to_string(&input)?;
+
+    assert!(out_dir
+        .join("VendorID=1")
+        .join("payment_type=Cash")
+        .join("part-0.parquet")
+        .is_file());
+
+    let input = formatdoc! {r#"
+        parquet("{}") |
+            count(VendorID, payment_type) |
+            show()
+    "#, out_dir.display()};
+
+    assert_interpreter!(
+        &input,
+        indoc!(
+            r#"
+            shape: (8, 3)
+            VendorID|payment_type|n
+            i64|str|u32
+            ---
+            1|Cash|12
+            1|Credit card|37
+            1|No charge|1
+            1|Unknown|5
+            2|Cash|41
+            2|Credit card|148
+            2|Dispute|2
+            2|Unknown|4
+            ---
+        "#
+        )
+    );
+
+    // Cannot write to a non empty directory without overwrite.
+    let input = formatdoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            parquet("{}", partition_by = VendorID)
+    "#, out_dir.display()};
+
+    assert_interpreter_error!(
+        &input,
+        format!(
+            "in step 2 (`parquet`) at line 2: parquet error: directory '{}' is not empty",
+            out_dir.display()
+        )
+    );
+
+    // Overwriting with other keys removes the old partitions.
+    let input = formatdoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, total_amount) |
+            parquet("{}", partition_by = payment_type, overwrite = true)
+        parquet("{}") |
+            count(payment_type) |
+            show()
+    "#, out_dir.display(), out_dir.display()};
+
+    assert_interpreter!(
+        &input,
+        indoc!(
+            r#"
+            shape: (5, 2)
+            payment_type|n
+            str|u32
+            ---
+            Cash|53
+            Credit card|185
+            Dispute|2
+            No charge|1
+            Unknown|9
+            ---
+        "#
+        )
+    );
+
+    assert!(!out_dir.join("VendorID=1").exists());
+
+    // Files that are not partitions are never removed.
+    fs::write(out_dir.join("notes.txt"), "notes")?;
+
+    let input = formatdoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            parquet("{}", partition_by = VendorID, overwrite = true)
+    "#, out_dir.display()};
+
+    assert_interpreter_error!(
+        &input,
+        format!(
+            "in step 2 (`parquet`) at line 2: parquet error: cannot overwrite directory '{}', '{}' is not a partition",
+            out_dir.display(),
+            out_dir.join("notes.txt").display()
+        )
+    );
+
+    assert!(out_dir.join("notes.txt").is_file());
+
+    fs::remove_dir_all(&out_dir)?;
+
+    Ok(())
+}
+
+#[test]
+fn parquet_list_of_files() -> Result<()> {
+    let first = std::env::temp_dir().join("dply_parquet_list_first.parquet");
+    let second = std::env::temp_dir().join("dply_parquet_list_second.parquet");
+
+    let input = formatdoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, total_amount) |
+            filter(payment_type == "No charge") |
+            parquet("{}", overwrite = true)
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, total_amount) |
+            filter(payment_type == "Dispute") |
+            parquet("{}", overwrite = true)
+    "#, first.display(), second.display()};
+    dply::interpreter::eval_to_string(&input)?;
+
+    // Files are concatenated in the list order.
+    let input = formatdoc! {r#"
+        parquet(["{}", "{}"]) |
+            show()
+    "#, second.display(), first.display()};
+
+    assert_interpreter!(
+        &input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            payment_type|total_amount
+            str|f64
+            ---
+            Dispute|7.3
+            Dispute|-8.3
+            No charge|8.8
+            ---
+       "#
+        )
+    );
+
+    let input = formatdoc! {r#"
+        parquet(["{}", "tests/data/lists.parquet"])
+    "#, first.display()};
+
+    assert_interpreter_error!(
+        &input,
+        format!(
+            "in step 1 (`parquet`) at line 1: parquet error: file 'tests/data/lists.parquet' \
+             schema doesn't match file '{}'",
+            first.display()
+        )
+    );
+
+    fs::remove_file(first)?;
+    fs::remove_file(second)?;
+
+    Ok(())
+}
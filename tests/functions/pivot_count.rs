@@ -0,0 +1,83 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn pivot_count() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            pivot_count(VendorID, payment_type) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 6)
+            VendorID|Cash|Credit card|Dispute|No charge|Unknown
+            i64|u32|u32|u32|u32|u32
+            ---
+            1|12|37|0|1|5
+            2|41|148|2|0|4
+            ---
+        "#
+        )
+    );
+
+    // Numeric values are sorted before being converted to column names.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            pivot_count(payment_type, passenger_count) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 8)
+            payment_type|0|1|2|3|4|5|6
+            str|u32|u32|u32|u32|u32|u32|u32
+            ---
+            Cash|0|36|7|4|3|2|1
+            Credit card|1|144|21|8|3|8|0
+            Dispute|0|1|1|0|0|0|0
+            No charge|0|1|0|0|0|0|0
+            Unknown|0|0|0|0|0|0|0
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pivot_count_errors() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            pivot_count(payment_type, payment_type)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`pivot_count`) at line 2: pivot_count error: index and values must be different columns"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(VendorID) |
+            pivot_count(payment_type, passenger_count)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`pivot_count`) at line 3: pivot_count error: must call summarize after a group_by"
+    );
+
+    Ok(())
+}
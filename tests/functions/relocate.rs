@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn relocate_default() -> Result<()> {
@@ -154,3 +154,20 @@ fn relocate_same_col() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn relocate_unknown_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            relocate(payment, total_amount, after = vendor)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`relocate`) at line 3: relocate error: Unknown columns: payment, vendor; \
+         available: VendorID, payment_type, total_amount"
+    );
+
+    Ok(())
+}
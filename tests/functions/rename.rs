@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn rename() -> Result<()> {
@@ -41,3 +41,74 @@ fn rename() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn rename_unknown_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            rename(vendor_id = vendor, payment = payment_type, amount = total)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`rename`) at line 3: rename error: Unknown columns: vendor, total; \
+         available: VendorID, payment_type, total_amount"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rename_duplicate_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, fare_amount, total_amount) |
+            rename(amount = fare_amount, amount = total_amount)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`rename`) at line 3: rename error: duplicate column name 'amount'"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, fare_amount, total_amount) |
+            rename(fare_amount = total_amount)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`rename`) at line 3: rename error: duplicate column name 'fare_amount'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rename_replace() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, ends_with("time"), total_amount) |
+            rename(replace("^tpep_", ""), vendor_id = VendorID) |
+            rename(replace("_(date)?time$", "_ts")) |
+            head(1)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 4)
+            vendor_id|pickup_ts|dropoff_ts|total_amount
+            i64|datetime[ns]|datetime[ns]|f64
+            ---
+            2|2022-11-22 19:27:01|2022-11-22 19:45:53|22.56
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn reorder_like() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount, payment_type, VendorID) |
+            trips
+        parquet("tests/data/nyctaxi.parquet") |
+            reorder_like(trips) |
+            head(2)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (2, 3)
+            total_amount|payment_type|VendorID
+            f64|str|i64
+            ---
+            22.56|Credit card|2
+            9.8|Cash|2
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount, payment_type, VendorID) |
+            trips
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, tip_amount) |
+            reorder_like(trips)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`reorder_like`) at line 6: reorder_like error: Unknown columns: total_amount, payment_type; \
+        available: VendorID, tip_amount"
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            reorder_like(trips)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`reorder_like`) at line 2: reorder_like error: undefined variable 'trips'"
+    );
+
+    Ok(())
+}
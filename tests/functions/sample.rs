@@ -0,0 +1,239 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use dply::interpreter;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn sample_n() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            sample_n(10) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            10
+            ---
+        "#
+        )
+    );
+
+    // The same seed returns the same rows.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            sample_n(5, seed = 42) |
+            show()
+    "#};
+    assert_eq!(
+        interpreter::eval_to_string(input)?,
+        interpreter::eval_to_string(input)?
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            sample_n(2.5)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 2 (`sample_n`) at line 2: sample_n error: the number of rows must be a non negative integer"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sample_n_group() -> Result<()> {
+    // Each group has at most 3 rows, smaller groups keep all their rows.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            sample_n(3) |
+            count(payment_type, sort = true) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (5, 2)
+            payment_type|n
+            str|u32
+            ---
+            Cash|3
+            Credit card|3
+            Unknown|3
+            Dispute|2
+            No charge|1
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            sample_n(3, seed = 42) |
+            show()
+    "#};
+    assert_eq!(
+        interpreter::eval_to_string(input)?,
+        interpreter::eval_to_string(input)?
+    );
+
+    // Groups of the same size get different seeds and sample different positions.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            mutate(r = row(), g = r % 2) |
+            filter(r < 20) |
+            select(g, r) |
+            group_by(g) |
+            sample_n(3, seed = 42) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (6, 2)
+            g|r
+            u64|u64
+            ---
+            1|3
+            1|11
+            1|13
+            0|6
+            0|8
+            0|10
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sample_frac_group() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            sample_frac(0.2) |
+            count(payment_type, sort = true) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            payment_type|n
+            str|u32
+            ---
+            Credit card|37
+            Cash|10
+            Unknown|1
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            sample_frac(1.5)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`sample_frac`) at line 3: sample_frac error: the fraction must be between 0 and 1"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sample_n_before_other_steps() -> Result<()> {
+    // The filter runs on the sampled rows, 20 of the 250 rows have
+    // total_amount > 50 and 10 of them are in the sample.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            sample_n(100, seed = 1) |
+            filter(total_amount > 50) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            10
+            ---
+        "#
+        )
+    );
+
+    // Filtering before sampling would return all the sampled rows.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            sample_n(10, seed = 1) |
+            filter(total_amount > 50) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            0
+            ---
+        "#
+        )
+    );
+
+    // The group columns are kept until the groups are sampled.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            sample_n(2, seed = 1) |
+            select(total_amount) |
+            count_rows()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            n
+            u32
+            ---
+            9
+            ---
+        "#
+        )
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn select_columns() -> Result<()> {
@@ -251,3 +251,82 @@ fn select_not_contains() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn select_unknown_columns() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            select(vendor, payment_type, amount = total, VendorID)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`select`) at line 3: select error: Unknown columns: vendor, total; \
+         available: VendorID, payment_type, total_amount"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn select_duplicate_columns() -> Result<()> {
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | select(total_amount, total_amount)"#,
+        "in step 2 (`select`) at line 1: select error: duplicate column name 'total_amount'"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | select(amount = fare_amount, amount = total_amount)"#,
+        "in step 2 (`select`) at line 1: select error: duplicate column name 'amount'"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/nyctaxi.parquet") | select(contains("amount"), fare_amount = tip_amount)"#,
+        "in step 2 (`select`) at line 1: select error: duplicate column name 'fare_amount'"
+    );
+
+    // A column matched by a pattern and by name is selected once.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(total_amount, contains("total")) |
+            head(1)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            total_amount
+            f64
+            ---
+            22.56
+            ---
+       "#
+        )
+    );
+
+    // A column can be renamed to `like`.
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(like = total_amount) |
+            head(1)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (1, 1)
+            like
+            f64
+            ---
+            22.56
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
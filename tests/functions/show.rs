@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn show() -> Result<()> {
@@ -116,3 +116,38 @@ fn show_timestamps() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn show_timezone() -> Result<()> {
+    // Naive datetimes are shown as UTC converted to the display timezone.
+    let input = indoc! {r#"
+        config(timezone = "America/New_York")
+        parquet("tests/data/timestamps.parquet") |
+            select(secs) |
+            mutate(rome = timezone_convert(secs, "UTC", "Europe/Rome")) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            secs|rome
+            datetime[ms, America/New_York]|datetime[ms, America/New_York]
+            ---
+            2023-08-02 19:09:42 EDT|2023-08-02 19:09:42 EDT
+            2023-08-02 19:09:46 EDT|2023-08-02 19:09:46 EDT
+            2023-08-02 19:09:54 EDT|2023-08-02 19:09:54 EDT
+            ---
+            "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"config(timezone = "Mars/Base")"#,
+        "in step 1 (`config`) at line 1: config error: unknown timezone 'Mars/Base'"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::assert_interpreter_error;
+
+#[cfg(feature = "sqlite")]
+use super::assert_interpreter;
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn sqlite_table() -> Result<()> {
+    let input = indoc! {r#"
+        sqlite("tests/data/zones.sqlite", "zones") |
+            head(3)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 4)
+            LocationID|Borough|Zone|service_zone
+            i64|str|str|str
+            ---
+            1|EWR|Newark Airport|EWR
+            2|Queens|Jamaica Bay|Boro Zone
+            3|Bronx|Allerton/Pelham Gardens|Boro Zone
+            ---
+       "#
+        )
+    );
+
+    let input = indoc! {r#"
+        sqlite("tests/data/zones.sqlite", "zones") | zones
+        parquet("tests/data/nyctaxi.parquet") |
+            left_join(zones, PULocationID == LocationID) |
+            count(Borough, sort = true) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            Borough|n
+            str|u32
+            ---
+            Manhattan|223
+            Queens|24
+            Unknown|3
+            ---
+       "#
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "sqlite")]
+fn sqlite_query() -> Result<()> {
+    let input = indoc! {r#"
+        sqlite(
+            "tests/data/zones.sqlite",
+            query = "select Borough, count(*) as zones, avg(LocationID) as avg_id
+                     from zones group by Borough order by Borough"
+        ) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (7, 3)
+            Borough|zones|avg_id
+            str|i64|f64
+            ---
+            Bronx|43|146.837209
+            Brooklyn|61|108.393443
+            EWR|1|1.0
+            Manhattan|69|147.434783
+            Queens|69|128.565217
+            Staten Island|20|137.25
+            Unknown|2|264.5
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"sqlite("tests/data/zones.sqlite", "boroughs") | show()"#,
+        "in step 1 (`sqlite`) at line 1: sqlite error: no such table: boroughs"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "sqlite"))]
+fn sqlite_disabled() -> Result<()> {
+    let input = indoc! {r#"
+        sqlite("tests/data/zones.sqlite", "zones") |
+            show()
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 1 (`sqlite`) at line 1: sqlite error: dply was built without the sqlite feature"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn struct_fields() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/structs.parquet") |
+            struct_fields(points)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 2)
+            field|type
+            str|str
+            ---
+            ptag|str
+            pid|i32
+            x|f32
+            y|f32
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/structs.parquet") | struct_fields(shape_id)"#,
+        "in step 2 (`struct_fields`) at line 1: struct_fields error: column 'shape_id' \
+         must be a struct, found u32"
+    );
+
+    Ok(())
+}
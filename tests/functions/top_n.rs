@@ -0,0 +1,75 @@
+// Copyright (C) 2023 Vince Vasta
+// SPDX-License-Identifier: Apache-2.0
+use anyhow::Result;
+use indoc::indoc;
+
+use super::{assert_interpreter, assert_interpreter_error};
+
+#[test]
+fn top_n() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(payment_type, total_amount) |
+            top_n(3, total_amount) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (3, 2)
+            payment_type|total_amount
+            str|f64
+            ---
+            Credit card|84.36
+            Credit card|77.64
+            Credit card|77.6
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            select(VendorID, payment_type, total_amount) |
+            group_by(payment_type) |
+            top_n(2, total_amount) |
+            show()
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (9, 3)
+            VendorID|payment_type|total_amount
+            i64|str|f64
+            ---
+            2|Credit card|84.36
+            2|Credit card|77.64
+            2|Cash|63.1
+            1|Cash|61.85
+            2|Unknown|54.47
+            2|Unknown|45.5
+            1|No charge|8.8
+            2|Dispute|7.3
+            2|Dispute|-8.3
+            ---
+        "#
+        )
+    );
+
+    let input = indoc! {r#"
+        parquet("tests/data/nyctaxi.parquet") |
+            group_by(payment_type) |
+            top_n(0, total_amount)
+    "#};
+
+    assert_interpreter_error!(
+        input,
+        "in step 3 (`top_n`) at line 3: top_n error: the number of rows must be a positive integer"
+    );
+
+    Ok(())
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use indoc::indoc;
 
-use super::assert_interpreter;
+use super::{assert_interpreter, assert_interpreter_error};
 
 #[test]
 fn unnest_ints() -> Result<()> {
@@ -181,3 +181,47 @@ fn unnest_structs() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn unnest_list_struct() -> Result<()> {
+    let input = indoc! {r#"
+        parquet("tests/data/structs.parquet") |
+            unnest_list(points) |
+            unnest_struct(points) |
+            head(4)
+    "#};
+
+    assert_interpreter!(
+        input,
+        indoc!(
+            r#"
+            shape: (4, 5)
+            shape_id|ptag|pid|x|y
+            u32|str|i32|f32|f32
+            ---
+            1|s1|0|-7.144482|-2.752852
+            1|s1|1|-3.377404|-2.862458
+            1|s1|2|-4.05302|6.336014
+            2|null|null|null|null
+            ---
+       "#
+        )
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/structs.parquet") | unnest_struct(points)"#,
+        "in step 2 (`unnest_struct`) at line 1: unnest_struct error: 'points' is a list, use unnest_list"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/structs.parquet") | unnest_list(points, points)"#,
+        "in step 2 (`unnest_list`) at line 1: unnest_list error: 'points' is a struct, use unnest_struct"
+    );
+
+    assert_interpreter_error!(
+        r#"parquet("tests/data/structs.parquet") | unnest_list(shape_id)"#,
+        "in step 2 (`unnest_list`) at line 1: unnest_list error: 'shape_id' is not a list type"
+    );
+
+    Ok(())
+}